@@ -0,0 +1,121 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    net::Ipv4Addr,
+};
+
+use pnet::packet::{
+    arp::{ArpOperations, ArpPacket},
+    ethernet::{EtherTypes, EthernetPacket},
+    Packet,
+};
+
+/// Represents an ARP request or reply received over Ethernet.
+pub struct ArpPdu<'a> {
+    bytes: &'a [u8],
+    pub request: bool,
+    pub sender_mac: String,
+    pub sender_ip: Ipv4Addr,
+    pub target_ip: Ipv4Addr,
+}
+
+impl<'a> ArpPdu<'a> {
+    // Attempt to create a new ARP PDU with the given packet.
+    pub fn new(bytes: &'a [u8]) -> Option<Self> {
+        let ethernet = EthernetPacket::new(bytes)?;
+        if ethernet.get_ethertype() != EtherTypes::Arp {
+            return None;
+        }
+        let arp = ArpPacket::new(ethernet.payload())?;
+        let request = match arp.get_operation() {
+            ArpOperations::Request => true,
+            ArpOperations::Reply => false,
+            _ => return None,
+        };
+
+        Some(Self {
+            bytes,
+            request,
+            sender_mac: arp.get_sender_hw_addr().to_string().to_uppercase(),
+            sender_ip: arp.get_sender_proto_addr(),
+            target_ip: arp.get_target_proto_addr(),
+        })
+    }
+
+    /// Gratuitous ARPs announce the sender's own address.
+    pub fn is_gratuitous(&self) -> bool {
+        self.sender_ip == self.target_ip
+    }
+
+    /// Check if the sender MAC is a VRRP or HSRP virtual router MAC.
+    pub fn is_virtual_router(&self) -> bool {
+        self.sender_mac.starts_with("00:00:5E:00:01:")
+            || self.sender_mac.starts_with("00:00:0C:07:AC:")
+            || self.sender_mac.starts_with("00:00:0C:9F:F")
+    }
+
+    pub fn print(&self) {
+        println!();
+        match (self.request, self.is_gratuitous()) {
+            (_, true) => println!("ARP:    Gratuitous"),
+            (true, false) => println!("ARP:    Request"),
+            (false, false) => println!("ARP:    Reply"),
+        }
+        println!("Sender: {} ({})", self.sender_ip, self.sender_mac);
+        println!("Target: {}", self.target_ip);
+        println!("Bytes:  {:02X?}", self.bytes);
+        println!();
+    }
+}
+
+/// Collects the ARP traffic seen during a listen window to guess the default gateway.
+#[derive(Default)]
+pub struct ArpTable {
+    pub macs: BTreeMap<Ipv4Addr, String>,
+    pub requests: BTreeMap<Ipv4Addr, u32>, // Number of requests for each target
+    pub virtual_routers: BTreeSet<Ipv4Addr>,
+}
+
+impl ArpTable {
+    pub fn add(&mut self, pdu: &ArpPdu) {
+        if !pdu.sender_ip.is_unspecified() {
+            self.macs.insert(pdu.sender_ip, pdu.sender_mac.to_string());
+            if pdu.is_virtual_router() {
+                self.virtual_routers.insert(pdu.sender_ip);
+            }
+        }
+        if pdu.request && !pdu.is_gratuitous() {
+            *self.requests.entry(pdu.target_ip).or_insert(0) += 1;
+        }
+    }
+
+    /// Return the IP of the likely gateway. A VRRP/HSRP virtual router is
+    /// preferred, otherwise the most requested IP since every host on the
+    /// VLAN resolves its gateway.
+    pub fn gateway(&self) -> Option<Ipv4Addr> {
+        if let Some(ip) = self.virtual_routers.iter().next() {
+            return Some(*ip);
+        }
+        self.requests
+            .iter()
+            .max_by_key(|(_, count)| **count)
+            .map(|(ip, _)| *ip)
+    }
+
+    /// Return the MAC of the gateway if it has answered or announced itself.
+    pub fn gateway_mac(&self) -> String {
+        self.gateway()
+            .and_then(|ip| self.macs.get(&ip))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub fn print(&self) {
+        println!();
+        match self.gateway() {
+            Some(ip) => println!("Gateway: {} ({})", ip, self.gateway_mac()),
+            None => println!("Gateway: "),
+        }
+        println!("ARP:     {} hosts seen", self.macs.len());
+        println!();
+    }
+}
@@ -0,0 +1,272 @@
+use std::{
+    fmt,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+};
+
+use crate::util::remove_chars;
+
+#[allow(non_snake_case)]
+pub mod TlvType {
+    pub const DEVICE_ID: u16 = 0x0001;
+    pub const ADDRESSES: u16 = 0x0002;
+    pub const PORT_ID: u16 = 0x0003;
+    pub const SOFTWARE_VERSION: u16 = 0x0005;
+    pub const PLATFORM: u16 = 0x0006;
+    pub const NATIVE_VLAN: u16 = 0x000A;
+    pub const DUPLEX: u16 = 0x000B;
+    pub const APPLIANCE_VLAN: u16 = 0x000E;
+}
+
+/// LLC/SNAP header that precedes every CDP PDU (DSAP, SSAP, control, OUI, PID).
+const SNAP_HEADER: [u8; 8] = [0xAA, 0xAA, 0x03, 0x00, 0x00, 0x0C, 0x20, 0x00];
+
+/// A single entry of a CDP style address list.
+pub struct Address {
+    pub protocol_type: u8,
+    pub protocol: Vec<u8>,
+    pub address: Vec<u8>,
+}
+
+impl Address {
+    /// Parse an address list: a 4 byte entry count followed by the entries.
+    pub fn parse_list(value: &[u8]) -> Vec<Address> {
+        let mut addresses = Vec::<Address>::new();
+        if value.len() < 4 {
+            return addresses;
+        }
+
+        let count = u32::from_be_bytes([value[0], value[1], value[2], value[3]]);
+        let mut index = 4;
+        for _ in 0..count {
+            // Protocol type (1), protocol length (1)
+            if index + 2 > value.len() {
+                break;
+            }
+            let protocol_type = value[index];
+            let protocol_len = value[index + 1] as usize;
+            index += 2;
+
+            // Protocol (n), address length (2)
+            if index + protocol_len + 2 > value.len() {
+                break;
+            }
+            let protocol = value[index..index + protocol_len].to_vec();
+            index += protocol_len;
+            let address_len = u16::from_be_bytes([value[index], value[index + 1]]) as usize;
+            index += 2;
+
+            // Address (n)
+            if index + address_len > value.len() {
+                break;
+            }
+            let address = value[index..index + address_len].to_vec();
+            index += address_len;
+
+            addresses.push(Address {
+                protocol_type,
+                protocol,
+                address,
+            });
+        }
+        addresses
+    }
+
+    /// The address as an IP address, if it is IPv4 or IPv6.
+    pub fn ip(&self) -> Option<IpAddr> {
+        match (self.protocol_type, self.protocol.as_slice()) {
+            // NLPID
+            (0x01, [0xCC]) => match <[u8; 4]>::try_from(self.address.as_slice()) {
+                Ok(octets) => Some(IpAddr::V4(Ipv4Addr::from(octets))),
+                Err(_) => None,
+            },
+            // 802.2 (LLC/SNAP + ethertype)
+            (0x02, [0xAA, 0xAA, 0x03, 0x00, 0x00, 0x00, 0x08, 0x00]) => {
+                match <[u8; 4]>::try_from(self.address.as_slice()) {
+                    Ok(octets) => Some(IpAddr::V4(Ipv4Addr::from(octets))),
+                    Err(_) => None,
+                }
+            }
+            (0x02, [0xAA, 0xAA, 0x03, 0x00, 0x00, 0x00, 0x86, 0xDD]) => {
+                match <[u8; 16]>::try_from(self.address.as_slice()) {
+                    Ok(octets) => Some(IpAddr::V6(Ipv6Addr::from(octets))),
+                    Err(_) => None,
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.ip() {
+            Some(ip) => write!(f, "{}", ip),
+            None => write!(f, "{:02X?}", self.address),
+        }
+    }
+}
+
+/// Represents a CDPv1/v2 PDU received over 802.3/LLC/SNAP.
+pub struct CdpPdu<'a> {
+    bytes: &'a [u8],
+    valid: bool,
+    pub version: u8,
+    pub ttl: u8,
+    pub switch_name: String,
+    pub switch_ip: String,
+    pub switch_addresses: Vec<Address>,
+    pub switch_port: String,
+    pub switch_platform: String,
+    pub switch_version: String,
+    pub switch_duplex: String,
+    pub switch_vlan_d: String,
+    pub switch_vlan_v: String,
+}
+
+impl<'a> CdpPdu<'a> {
+    // Attempt to create a new CDP PDU with the given packet.
+    pub fn new(bytes: &'a [u8]) -> Option<Self> {
+        // 802.3 header (14), LLC/SNAP (8), CDP header (4)
+        if bytes.len() < 26 || bytes[14..22] != SNAP_HEADER {
+            return None;
+        }
+
+        let mut pdu = Self {
+            bytes,
+            valid: false,
+            version: bytes[22],
+            ttl: bytes[23],
+            switch_name: String::new(),
+            switch_ip: String::new(),
+            switch_addresses: Vec::new(),
+            switch_port: String::new(),
+            switch_platform: String::new(),
+            switch_version: String::new(),
+            switch_duplex: String::new(),
+            switch_vlan_d: String::new(),
+            switch_vlan_v: String::new(),
+        };
+
+        // Parse TLVs
+        let mut index = 26;
+        while index + 4 <= bytes.len() {
+            // Type/Length, the length includes the 4 header bytes.
+            let t = u16::from_be_bytes([bytes[index], bytes[index + 1]]);
+            let l = u16::from_be_bytes([bytes[index + 2], bytes[index + 3]]) as usize;
+            if l < 4 || (index + l) > bytes.len() {
+                break;
+            }
+            let value = &bytes[index + 4..index + l];
+
+            match t {
+                TlvType::DEVICE_ID => {
+                    if let Ok(value) = std::str::from_utf8(value) {
+                        pdu.switch_name = value.trim_end_matches('\0').to_string();
+                        pdu.valid = true;
+                    }
+                }
+                TlvType::ADDRESSES => {
+                    pdu.switch_addresses = Address::parse_list(value);
+                    if let Some(ip) = pdu
+                        .switch_addresses
+                        .iter()
+                        .filter_map(|address| address.ip())
+                        .find(|ip| ip.is_ipv4())
+                    {
+                        pdu.switch_ip = ip.to_string();
+                    }
+                }
+                TlvType::PORT_ID => {
+                    if let Ok(value) = std::str::from_utf8(value) {
+                        pdu.switch_port = remove_chars(value);
+                    }
+                }
+                TlvType::SOFTWARE_VERSION => {
+                    if let Ok(value) = std::str::from_utf8(value) {
+                        pdu.switch_version = value.trim().to_string();
+                    }
+                }
+                TlvType::PLATFORM => {
+                    if let Ok(value) = std::str::from_utf8(value) {
+                        pdu.switch_platform = value.trim().to_string();
+                    }
+                }
+                TlvType::NATIVE_VLAN if value.len() >= 2 => {
+                    let vlan_data = u16::from_be_bytes([value[0], value[1]]);
+                    pdu.switch_vlan_d = vlan_data.to_string();
+                }
+                TlvType::DUPLEX => {
+                    if let Some(duplex) = value.first() {
+                        pdu.switch_duplex = match duplex {
+                            0x00 => String::from("Half"),
+                            _ => String::from("Full"),
+                        };
+                    }
+                }
+                TlvType::APPLIANCE_VLAN if value.len() >= 3 => {
+                    // Appliance ID (1), VLAN (2)
+                    let vlan_voice = u16::from_be_bytes([value[1], value[2]]);
+                    pdu.switch_vlan_v = vlan_voice.to_string();
+                }
+                _ => {}
+            }
+
+            // Go to the next TLV
+            index += l;
+        }
+
+        if pdu.valid {
+            Some(pdu)
+        } else {
+            None
+        }
+    }
+
+    pub fn switch(&self) -> String {
+        let mut switch = String::new();
+        if !self.switch_name.is_empty() {
+            switch.push_str(&self.switch_name);
+        }
+        if !self.switch_ip.is_empty() {
+            if !switch.is_empty() {
+                switch.push(' ');
+            }
+            switch.push_str(&format!("({})", &self.switch_ip));
+        }
+        switch
+    }
+
+    pub fn switch_display(&self) -> String {
+        self.switch_name.to_string()
+    }
+
+    /// Return the VLANs in a String separated by a comma if there is
+    /// more than one.
+    pub fn vlan(&self) -> String {
+        let mut vlan = String::new();
+        if !self.switch_vlan_d.is_empty() {
+            vlan.push_str(&self.switch_vlan_d);
+        }
+        if !self.switch_vlan_v.is_empty() {
+            if !vlan.is_empty() {
+                vlan.push_str(", ");
+            }
+            vlan.push_str(&self.switch_vlan_v);
+        }
+        vlan
+    }
+
+    pub fn print(&self) {
+        println!();
+        println!("Switch:   {} ({})", self.switch_name, self.switch_ip);
+        println!("Platform: {}", self.switch_platform);
+        println!("Port:     {}", self.switch_port);
+        println!("Duplex:   {}", self.switch_duplex);
+        println!("Data:     {}", self.switch_vlan_d);
+        println!("Voice:    {}", self.switch_vlan_v);
+        println!("Software: {}", self.switch_version);
+        println!("CDP:      v{} (TTL {}s)", self.version, self.ttl);
+        println!("Bytes:    {:02X?}", self.bytes);
+        println!();
+    }
+}
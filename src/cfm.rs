@@ -0,0 +1,158 @@
+use crate::util::hex_string;
+
+/// CFM ethertype, CCMs are sent to 01:80:C2:00:00:3x where x is the MD level.
+pub const ETHERTYPE: u16 = 0x8902;
+
+#[allow(non_snake_case)]
+pub mod Opcode {
+    pub const CCM: u8 = 0x01;
+}
+
+#[allow(non_snake_case)]
+pub mod MdNameFormat {
+    pub const NONE: u8 = 0x01;
+    pub const DNS: u8 = 0x02;
+    pub const MAC_AND_UINT: u8 = 0x03;
+    pub const STRING: u8 = 0x04;
+}
+
+#[allow(non_snake_case)]
+pub mod MaNameFormat {
+    pub const PRIMARY_VID: u8 = 0x01;
+    pub const STRING: u8 = 0x02;
+    pub const UINT: u8 = 0x03;
+    pub const ICC: u8 = 0x20;
+}
+
+/// Remote Defect Indication flag, the low 3 bits of the flags are the CCM interval.
+const RDI: u8 = 0x80;
+
+/// Represents an IEEE 802.1ag/Y.1731 Continuity Check Message.
+pub struct CfmPdu<'a> {
+    bytes: &'a [u8],
+    pub md_level: u8,
+    pub version: u8,
+    pub flags: u8,
+    pub sequence_number: u32,
+    pub mep_id: u16,
+    pub md_name: String,
+    pub ma_name: String,
+}
+
+impl<'a> CfmPdu<'a> {
+    // Attempt to create a new CFM CCM with the given packet.
+    pub fn new(bytes: &'a [u8]) -> Option<Self> {
+        // Ethernet header (14), CFM header (4), sequence number (4), MEP ID (2), MAID (48)
+        if bytes.len() < 72
+            || u16::from_be_bytes([bytes[12], bytes[13]]) != ETHERTYPE
+            || bytes[15] != Opcode::CCM
+        {
+            return None;
+        }
+
+        // MD level (3 bits), version (5 bits), opcode (1), flags (1), first TLV offset (1)
+        let ccm = &bytes[14..];
+        let (md_name, ma_name) = Self::parse_maid(&ccm[10..58])?;
+        Some(Self {
+            bytes,
+            md_level: ccm[0] >> 5,
+            version: ccm[0] & 0x1F,
+            flags: ccm[2],
+            sequence_number: u32::from_be_bytes([ccm[4], ccm[5], ccm[6], ccm[7]]),
+            mep_id: u16::from_be_bytes([ccm[8], ccm[9]]) & 0x1FFF,
+            md_name,
+            ma_name,
+        })
+    }
+
+    /// MD name format (1), MD name length (1), MD name (n), short MA name format (1),
+    /// short MA name length (1), short MA name (n). There is no MD name length or
+    /// name if the format is none.
+    fn parse_maid(maid: &[u8]) -> Option<(String, String)> {
+        let mut index = 1;
+        let mut md_name = String::new();
+        if maid[0] != MdNameFormat::NONE {
+            let l = maid[1] as usize;
+            if 2 + l + 2 > maid.len() {
+                return None;
+            }
+            let name = &maid[2..2 + l];
+            md_name = match maid[0] {
+                MdNameFormat::DNS | MdNameFormat::STRING => {
+                    String::from_utf8_lossy(name).to_string()
+                }
+                MdNameFormat::MAC_AND_UINT if l == 8 => format!(
+                    "{}:{}",
+                    hex_string(&name[0..6]),
+                    u16::from_be_bytes([name[6], name[7]])
+                ),
+                _ => Self::hex_digits(name),
+            };
+            index = 2 + l;
+        }
+
+        let format = maid[index];
+        let l = maid[index + 1] as usize;
+        if index + 2 + l > maid.len() {
+            return None;
+        }
+        let name = &maid[index + 2..index + 2 + l];
+        let ma_name = match format {
+            MaNameFormat::PRIMARY_VID if l == 2 => {
+                format!("VID {}", u16::from_be_bytes([name[0], name[1]]) & 0x0FFF)
+            }
+            MaNameFormat::STRING | MaNameFormat::ICC => String::from_utf8_lossy(name).to_string(),
+            MaNameFormat::UINT if l == 2 => u16::from_be_bytes([name[0], name[1]]).to_string(),
+            // RFC 2685 VPN IDs and unknown formats
+            _ => Self::hex_digits(name),
+        };
+        Some((md_name, ma_name))
+    }
+
+    /// Return the maintenance association ID as "MD/MA", or only the MA if
+    /// there is no MD name.
+    pub fn maid(&self) -> String {
+        if self.md_name.is_empty() {
+            self.ma_name.to_string()
+        } else {
+            format!("{}/{}", self.md_name, self.ma_name)
+        }
+    }
+
+    pub fn rdi(&self) -> bool {
+        self.flags & RDI != 0
+    }
+
+    pub fn interval(&self) -> &'static str {
+        match self.flags & 0x07 {
+            1 => "3.33ms",
+            2 => "10ms",
+            3 => "100ms",
+            4 => "1s",
+            5 => "10s",
+            6 => "1min",
+            7 => "10min",
+            _ => "Invalid",
+        }
+    }
+
+    pub fn print(&self) {
+        println!();
+        println!(
+            "CFM:      CCM v{} (Sequence {})",
+            self.version, self.sequence_number
+        );
+        println!("Level:    {}", self.md_level);
+        println!("MAID:     {}", self.maid());
+        println!("MEP:      {}", self.mep_id);
+        println!("Interval: {}", self.interval());
+        println!("RDI:      {}", self.rdi());
+        println!("Bytes:    {:02X?}", self.bytes);
+        println!();
+    }
+
+    /// Format bytes as unseparated hex, e.g. "0A1B2C".
+    fn hex_digits(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02X}", b)).collect()
+    }
+}
@@ -0,0 +1,202 @@
+/// EAPOL ethertype, usually sent to the PAE group address 01:80:C2:00:00:03.
+pub const ETHERTYPE: u16 = 0x888E;
+
+#[allow(non_snake_case)]
+pub mod PacketType {
+    pub const EAP: u8 = 0x00;
+    pub const START: u8 = 0x01;
+    pub const LOGOFF: u8 = 0x02;
+    pub const KEY: u8 = 0x03;
+}
+
+#[allow(non_snake_case)]
+pub mod EapCode {
+    pub const REQUEST: u8 = 0x01;
+    pub const RESPONSE: u8 = 0x02;
+    pub const SUCCESS: u8 = 0x03;
+    pub const FAILURE: u8 = 0x04;
+}
+
+#[allow(non_snake_case)]
+pub mod EapType {
+    pub const IDENTITY: u8 = 0x01;
+}
+
+/// An EAP packet carried by EAPOL.
+pub struct Eap {
+    pub code: u8,
+    pub identifier: u8,
+    pub eap_type: Option<u8>, // Only requests and responses have a type
+    pub data: String,         // Identity prompt or response
+}
+
+/// Represents an IEEE 802.1X EAPOL frame.
+pub struct EapolPdu<'a> {
+    bytes: &'a [u8],
+    pub version: u8,
+    pub packet_type: u8,
+    pub eap: Option<Eap>,
+}
+
+impl<'a> EapolPdu<'a> {
+    // Attempt to create a new EAPOL PDU with the given packet.
+    pub fn new(bytes: &'a [u8]) -> Option<Self> {
+        // Ethernet header (14), version (1), type (1), body length (2)
+        if bytes.len() < 18 || u16::from_be_bytes([bytes[12], bytes[13]]) != ETHERTYPE {
+            return None;
+        }
+
+        let packet_type = bytes[15];
+        let length = u16::from_be_bytes([bytes[16], bytes[17]]) as usize;
+        let body = &bytes[18..(18 + length).min(bytes.len())];
+
+        // Code (1), identifier (1), length (2), type (1), type data (n)
+        let mut eap = None;
+        if packet_type == PacketType::EAP && body.len() >= 4 {
+            let l = (u16::from_be_bytes([body[2], body[3]]) as usize).min(body.len());
+            let has_type = (body[0] == EapCode::REQUEST || body[0] == EapCode::RESPONSE) && l >= 5;
+            eap = Some(Eap {
+                code: body[0],
+                identifier: body[1],
+                eap_type: if has_type { Some(body[4]) } else { None },
+                data: if has_type && body[4] == EapType::IDENTITY {
+                    String::from_utf8_lossy(&body[5..l])
+                        .trim_end_matches('\0')
+                        .to_string()
+                } else {
+                    String::new()
+                },
+            });
+        }
+
+        Some(Self {
+            bytes,
+            version: bytes[14],
+            packet_type,
+            eap,
+        })
+    }
+
+    /// Return a short description of the frame, e.g. "EAP-Request/Identity".
+    pub fn message(&self) -> String {
+        match (self.packet_type, &self.eap) {
+            (PacketType::EAP, Some(eap)) => {
+                let code = match eap.code {
+                    EapCode::REQUEST => "Request",
+                    EapCode::RESPONSE => "Response",
+                    EapCode::SUCCESS => "Success",
+                    EapCode::FAILURE => "Failure",
+                    _ => "Unknown",
+                };
+                match eap.eap_type {
+                    Some(eap_type) => format!("EAP-{}/{}", code, Self::eap_type_name(eap_type)),
+                    None => format!("EAP-{}", code),
+                }
+            }
+            (PacketType::EAP, None) => String::from("EAP"),
+            (PacketType::START, _) => String::from("EAPOL-Start"),
+            (PacketType::LOGOFF, _) => String::from("EAPOL-Logoff"),
+            (PacketType::KEY, _) => String::from("EAPOL-Key"),
+            _ => String::from("Unknown"),
+        }
+    }
+
+    fn eap_type_name(eap_type: u8) -> &'static str {
+        match eap_type {
+            EapType::IDENTITY => "Identity",
+            2 => "Notification",
+            3 => "Nak",
+            4 => "MD5-Challenge",
+            13 => "TLS",
+            21 => "TTLS",
+            25 => "PEAP",
+            26 => "MSCHAPv2",
+            43 => "FAST",
+            _ => "Unknown",
+        }
+    }
+
+    /// Check if the frame came from the authenticator asking for an identity,
+    /// meaning 802.1X is enforced on the port.
+    pub fn is_identity_request(&self) -> bool {
+        match &self.eap {
+            Some(eap) => eap.code == EapCode::REQUEST && eap.eap_type == Some(EapType::IDENTITY),
+            None => false,
+        }
+    }
+
+    /// Check if the frame was sent by the authenticator rather than a supplicant.
+    /// This host's own EAPOL-Start/Logoff and EAP-Responses are captured too and
+    /// don't show the switch is enforcing 802.1X.
+    pub fn is_from_authenticator(&self) -> bool {
+        match (self.packet_type, &self.eap) {
+            (PacketType::EAP, Some(eap)) => eap.code != EapCode::RESPONSE,
+            (PacketType::KEY, _) => true,
+            _ => false,
+        }
+    }
+
+    pub fn print(&self) {
+        println!();
+        println!("EAPOL:    v{} {}", self.version, self.message());
+        if let Some(eap) = &self.eap {
+            println!("Id:       {}", eap.identifier);
+            if !eap.data.is_empty() {
+                println!("Identity: {}", eap.data);
+            }
+        }
+        println!("Bytes:    {:02X?}", self.bytes);
+        println!();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(packet_type: u8, eap: &[u8]) -> Vec<u8> {
+        let mut bytes = vec![0x01, 0x80, 0xC2, 0x00, 0x00, 0x03];
+        bytes.extend_from_slice(&[0x00, 0x11, 0x22, 0x33, 0x44, 0x55]);
+        bytes.extend_from_slice(&ETHERTYPE.to_be_bytes());
+        bytes.extend_from_slice(&[2, packet_type]);
+        bytes.extend_from_slice(&(eap.len() as u16).to_be_bytes());
+        bytes.extend_from_slice(eap);
+        bytes
+    }
+
+    #[test]
+    fn identity_request_is_from_authenticator() {
+        let bytes = frame(
+            PacketType::EAP,
+            &[EapCode::REQUEST, 1, 0, 5, EapType::IDENTITY],
+        );
+        let pdu = EapolPdu::new(&bytes).unwrap();
+        assert!(pdu.is_identity_request());
+        assert!(pdu.is_from_authenticator());
+        assert_eq!(pdu.message(), "EAP-Request/Identity");
+    }
+
+    #[test]
+    fn supplicant_frames_are_not_from_authenticator() {
+        let bytes = frame(PacketType::START, &[]);
+        assert!(!EapolPdu::new(&bytes).unwrap().is_from_authenticator());
+        let bytes = frame(PacketType::LOGOFF, &[]);
+        assert!(!EapolPdu::new(&bytes).unwrap().is_from_authenticator());
+
+        let response = [
+            EapCode::RESPONSE,
+            1,
+            0,
+            9,
+            EapType::IDENTITY,
+            b'h',
+            b'o',
+            b's',
+            b't',
+        ];
+        let bytes = frame(PacketType::EAP, &response);
+        let pdu = EapolPdu::new(&bytes).unwrap();
+        assert!(!pdu.is_from_authenticator());
+        assert_eq!(pdu.eap.unwrap().data, "host");
+    }
+}
@@ -0,0 +1,216 @@
+use std::net::Ipv4Addr;
+
+use crate::util::hex_string;
+
+#[allow(non_snake_case)]
+pub mod TlvType {
+    pub const NULL: u8 = 0x00;
+    pub const DISPLAY: u8 = 0x01;
+    pub const INFO: u8 = 0x02;
+    pub const VLAN: u8 = 0x05;
+}
+
+/// LLC/SNAP header that precedes every EDP PDU (DSAP, SSAP, control, OUI, PID).
+const SNAP_HEADER: [u8; 8] = [0xAA, 0xAA, 0x03, 0x00, 0xE0, 0x2B, 0x00, 0xBB];
+
+/// Every EDP TLV starts with this marker.
+const TLV_MARKER: u8 = 0x99;
+
+/// A VLAN advertised in an EDP VLAN TLV.
+pub struct EdpVlan {
+    pub vlan_id: u16,
+    pub name: String,
+    pub ip: Option<Ipv4Addr>,
+}
+
+/// Represents an Extreme Discovery Protocol PDU received over 802.3/LLC/SNAP.
+pub struct EdpPdu<'a> {
+    bytes: &'a [u8],
+    valid: bool,
+    pub version: u8,
+    pub switch_mac: String,
+    pub switch_name: String,
+    pub switch_ip: String,
+    pub switch_port: String,
+    pub switch_version: String,
+    pub switch_vlans: Vec<EdpVlan>,
+}
+
+impl<'a> EdpPdu<'a> {
+    // Attempt to create a new EDP PDU with the given packet.
+    pub fn new(bytes: &'a [u8]) -> Option<Self> {
+        // 802.3 header (14), LLC/SNAP (8), EDP header (16)
+        if bytes.len() < 38 || bytes[14..22] != SNAP_HEADER {
+            return None;
+        }
+
+        // The 802.3 length excludes any Ethernet padding after the PDU.
+        let length = u16::from_be_bytes([bytes[12], bytes[13]]) as usize;
+        let end = (14 + length).min(bytes.len());
+
+        // Version (1), reserved (1), length (2), checksum (2), sequence (2),
+        // machine ID type (2), machine MAC (6)
+        let mut pdu = Self {
+            bytes,
+            valid: false,
+            version: bytes[22],
+            switch_mac: hex_string(&bytes[32..38]),
+            switch_name: String::new(),
+            switch_ip: String::new(),
+            switch_port: String::new(),
+            switch_version: String::new(),
+            switch_vlans: Vec::new(),
+        };
+
+        // Parse TLVs
+        let mut index = 38;
+        while index + 4 <= end {
+            // Marker (1), type (1), length (2), the length includes the 4 header bytes.
+            let marker = bytes[index];
+            let t = bytes[index + 1];
+            let l = u16::from_be_bytes([bytes[index + 2], bytes[index + 3]]) as usize;
+            if marker != TLV_MARKER || l < 4 || (index + l) > end {
+                break;
+            }
+            let value = &bytes[index + 4..index + l];
+
+            match t {
+                TlvType::NULL => break,
+                TlvType::DISPLAY => {
+                    pdu.switch_name = String::from_utf8_lossy(value)
+                        .trim_end_matches('\0')
+                        .to_string();
+                    pdu.valid = true;
+                }
+                TlvType::INFO if value.len() >= 16 => {
+                    // Slot (2), port (2), virtual chassis (2), reserved (6), version (4)
+                    // Slots and ports are numbered from 0 on the wire.
+                    let slot = u16::from_be_bytes([value[0], value[1]]) as u32;
+                    let port = u16::from_be_bytes([value[2], value[3]]) as u32;
+                    pdu.switch_port = format!("{}/{}", slot + 1, port + 1);
+                    pdu.switch_version =
+                        format!("{}.{}.{}.{}", value[12], value[13], value[14], value[15]);
+                    pdu.valid = true;
+                }
+                TlvType::VLAN if value.len() >= 12 => {
+                    // Flags (1), reserved (1), VLAN ID (2), reserved (4), IP (4), name (n)
+                    let has_ip = value[0] & 0x80 != 0;
+                    let ip = Ipv4Addr::new(value[8], value[9], value[10], value[11]);
+                    let vlan = EdpVlan {
+                        vlan_id: u16::from_be_bytes([value[2], value[3]]),
+                        name: String::from_utf8_lossy(&value[12..])
+                            .trim_end_matches('\0')
+                            .to_string(),
+                        ip: if has_ip { Some(ip) } else { None },
+                    };
+                    if pdu.switch_ip.is_empty() {
+                        if let Some(ip) = vlan.ip {
+                            pdu.switch_ip = ip.to_string();
+                        }
+                    }
+                    pdu.switch_vlans.push(vlan);
+                }
+                _ => {}
+            }
+
+            // Go to the next TLV
+            index += l;
+        }
+
+        if pdu.valid {
+            Some(pdu)
+        } else {
+            None
+        }
+    }
+
+    pub fn switch(&self) -> String {
+        let mut switch = String::new();
+        if !self.switch_name.is_empty() {
+            switch.push_str(&self.switch_name);
+        }
+        if !self.switch_ip.is_empty() {
+            if !switch.is_empty() {
+                switch.push(' ');
+            }
+            switch.push_str(&format!("({})", &self.switch_ip));
+        }
+        switch
+    }
+
+    pub fn switch_display(&self) -> String {
+        self.switch_name.to_string()
+    }
+
+    /// Return the VLAN IDs in a String separated by a comma if there is
+    /// more than one.
+    pub fn vlan(&self) -> String {
+        self.switch_vlans
+            .iter()
+            .map(|vlan| vlan.vlan_id.to_string())
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+
+    /// Return the VLAN names in a String separated by a comma if there is
+    /// more than one.
+    pub fn vlan_names(&self) -> String {
+        self.switch_vlans
+            .iter()
+            .map(|vlan| format!("{} ({})", vlan.vlan_id, vlan.name))
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+
+    pub fn print(&self) {
+        println!();
+        println!("Switch:  {} ({})", self.switch_name, self.switch_ip);
+        println!("MAC:     {}", self.switch_mac);
+        println!("Port:    {}", self.switch_port);
+        println!("Vlan:    {}", self.vlan_names());
+        println!("Version: {} (EDPv{})", self.switch_version, self.version);
+        println!("Bytes:   {:02X?}", self.bytes);
+        println!();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build an EDP frame with a single info TLV for the given slot and port.
+    fn frame(slot: u16, port: u16) -> Vec<u8> {
+        let mut info = vec![0u8; 16];
+        info[0..2].copy_from_slice(&slot.to_be_bytes());
+        info[2..4].copy_from_slice(&port.to_be_bytes());
+        info[12..16].copy_from_slice(&[16, 1, 2, 3]);
+
+        let mut bytes = vec![0x00, 0xE0, 0x2B, 0x00, 0x00, 0x00];
+        bytes.extend_from_slice(&[0x00, 0x04, 0x96, 0x11, 0x22, 0x33]);
+        bytes.extend_from_slice(&[0, 0]); // Length, set below
+        bytes.extend_from_slice(&SNAP_HEADER);
+        bytes.extend_from_slice(&[1, 0, 0, 0, 0, 0, 0, 1, 0, 0]);
+        bytes.extend_from_slice(&[0x00, 0x04, 0x96, 0x11, 0x22, 0x33]);
+        bytes.extend_from_slice(&[TLV_MARKER, TlvType::INFO, 0, 20]);
+        bytes.extend_from_slice(&info);
+        let length = (bytes.len() - 14) as u16;
+        bytes[12..14].copy_from_slice(&length.to_be_bytes());
+        bytes
+    }
+
+    #[test]
+    fn parses_slot_and_port() {
+        let bytes = frame(0, 23);
+        let pdu = EdpPdu::new(&bytes).unwrap();
+        assert_eq!(pdu.switch_port, "1/24");
+        assert_eq!(pdu.switch_version, "16.1.2.3");
+        assert_eq!(pdu.switch_mac, "00:04:96:11:22:33");
+    }
+
+    #[test]
+    fn max_slot_and_port_do_not_overflow() {
+        let bytes = frame(0xFFFF, 0xFFFF);
+        let pdu = EdpPdu::new(&bytes).unwrap();
+        assert_eq!(pdu.switch_port, "65536/65536");
+    }
+}
@@ -1,3 +1,5 @@
+use crate::{cdp::Address, util::remove_chars};
+
 #[allow(non_snake_case)]
 pub mod TlvType {
     pub const DEVICE_ID: u16 = 0x0001;
@@ -10,12 +12,22 @@ pub mod TlvType {
     pub const TAG_INFO: u16 = 0x0108;
 }
 
+/// LLC/SNAP header that precedes every FDP PDU (DSAP, SSAP, control, OUI, PID).
+const SNAP_HEADER: [u8; 8] = [0xAA, 0xAA, 0x03, 0x00, 0xE0, 0x52, 0x20, 0x00];
+
 pub struct FdpPdu<'a> {
     bytes: &'a [u8],
     valid: bool,
+    pub version: u8,
+    pub ttl: u8, // Seconds the advertisement stays valid
+    pub checksum_valid: bool,
     pub switch_name: String,
     pub switch_ip: String,
+    pub switch_addresses: Vec<Address>,
     pub switch_port: String,
+    pub switch_capabilities: String,
+    pub switch_version: String,
+    pub switch_platform: String,
     pub switch_vlan_d: String,
     pub switch_vlan_v: String,
 }
@@ -23,14 +35,36 @@ pub struct FdpPdu<'a> {
 impl<'a> FdpPdu<'a> {
     // Attempt to create a new FDP PDU with the given packet.
     pub fn new(bytes: &'a [u8]) -> Option<Self> {
-        let bytes = bytes.clone();
+        // 802.3 header (14), LLC/SNAP (8), FDP header (4)
+        if bytes.len() < 26 || bytes[14..22] != SNAP_HEADER {
+            return None;
+        }
+
+        // The 802.3 length excludes any Ethernet padding after the PDU.
+        let length = u16::from_be_bytes([bytes[12], bytes[13]]) as usize;
+        if 14 + length < 26 {
+            return None;
+        }
+        let end = (14 + length).min(bytes.len());
+
+        // Version (1), TTL (1), checksum (2)
+        if bytes[22] != 1 && bytes[22] != 2 {
+            return None;
+        }
 
         let mut pdu = Self {
             bytes,
             valid: false,
+            version: bytes[22],
+            ttl: bytes[23],
+            checksum_valid: Self::checksum_valid(&bytes[22..end]),
             switch_name: String::new(),
             switch_ip: String::new(),
+            switch_addresses: Vec::new(),
             switch_port: String::new(),
+            switch_capabilities: String::new(),
+            switch_version: String::new(),
+            switch_platform: String::new(),
             switch_vlan_d: String::new(),
             switch_vlan_v: String::new(),
         };
@@ -38,12 +72,12 @@ impl<'a> FdpPdu<'a> {
         // Parse TLVs
         let mut index = 26;
         loop {
-            if (index + 1) < bytes.len() {
-                // Type/Length
+            if (index + 3) < end {
+                // Type/Length, the length includes the 4 header bytes.
                 let t = u16::from_be_bytes([bytes[index], bytes[index + 1]]) as usize;
                 let l = u16::from_be_bytes([bytes[index + 2], bytes[index + 3]]) as usize;
 
-                if (index + l) > bytes.len() {
+                if l < 4 || (index + l) > end {
                     break;
                 }
 
@@ -58,34 +92,48 @@ impl<'a> FdpPdu<'a> {
                         }
                     }
                     TlvType::NET => {
-                        // The IP of the switch is located in the last 4 bytes.
-                        let mut ip = String::new();
-                        for byte in &bytes[(index + l - 4)..=(index + l - 1)] {
-                            if ip.len() == 0 {
-                                ip.push_str(byte.to_string().as_str());
-                            } else {
-                                ip.push('.');
-                                ip.push_str(byte.to_string().as_str());
-                            }
+                        // Address list in the same format as CDP.
+                        pdu.switch_addresses = Address::parse_list(&bytes[index + 4..index + l]);
+                        if let Some(ip) = pdu
+                            .switch_addresses
+                            .iter()
+                            .filter_map(|address| address.ip())
+                            .find(|ip| ip.is_ipv4())
+                        {
+                            pdu.switch_ip = ip.to_string();
                         }
-                        pdu.switch_ip = ip;
                     }
                     TlvType::INTERFACE => {
                         if let Ok(value) = std::str::from_utf8(&bytes[index + 4..=(index + l - 1)])
                         {
-                            pdu.switch_port = Self::remove_chars(&value.to_string());
+                            pdu.switch_port = remove_chars(value);
+                        }
+                    }
+                    TlvType::CAPABILITIES => {
+                        if let Ok(value) = std::str::from_utf8(&bytes[index + 4..=(index + l - 1)])
+                        {
+                            pdu.switch_capabilities = value.trim().to_string();
+                        }
+                    }
+                    TlvType::VERSION => {
+                        if let Ok(value) = std::str::from_utf8(&bytes[index + 4..=(index + l - 1)])
+                        {
+                            pdu.switch_version = value.trim().to_string();
                         }
                     }
-                    TlvType::CAPABILITIES => {}
-                    TlvType::VERSION => {}
-                    TlvType::PLATFORM => {}
-                    TlvType::VLAN => {
+                    TlvType::PLATFORM => {
+                        if let Ok(value) = std::str::from_utf8(&bytes[index + 4..=(index + l - 1)])
+                        {
+                            pdu.switch_platform = value.trim().to_string();
+                        }
+                    }
+                    TlvType::VLAN if l >= 6 => {
                         let vlan_data = u16::from_be_bytes([bytes[index + 4], bytes[index + 5]]);
                         if pdu.switch_vlan_d.len() == 0 && vlan_data != 0 {
                             pdu.switch_vlan_d = vlan_data.to_string();
                         }
                     }
-                    TlvType::TAG_INFO => {
+                    TlvType::TAG_INFO if l >= 13 => {
                         // Bytes 2,3 & 7,8 contain vlan info.
                         let vlan_bytes = bytes[(index + 4)..=(index + l - 1)].to_vec();
                         let vlan_data = u16::from_be_bytes([vlan_bytes[2], vlan_bytes[3]]);
@@ -128,6 +176,15 @@ impl<'a> FdpPdu<'a> {
         self.switch_name.to_string()
     }
 
+    /// Return all management addresses in a String separated by a comma.
+    pub fn addresses(&self) -> String {
+        self.switch_addresses
+            .iter()
+            .map(|address| address.to_string())
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+
     /// Return the VLANs in a String separated by a comma if there is
     /// more than one.
     pub fn vlan(&self) -> String {
@@ -146,22 +203,114 @@ impl<'a> FdpPdu<'a> {
 
     pub fn print(&self) {
         println!("");
-        println!("Switch: {} ({})", self.switch_name, self.switch_ip);
-        println!("Port:   {}", self.switch_port);
-        println!("Data:   {}", self.switch_vlan_d);
-        println!("Voice:  {}", self.switch_vlan_v);
-        println!("Bytes:  {:02X?}", self.bytes);
+        println!("Switch:       {} ({})", self.switch_name, self.switch_ip);
+        println!("Addresses:    {}", self.addresses());
+        println!("Platform:     {}", self.switch_platform);
+        println!("Capabilities: {}", self.switch_capabilities);
+        println!("Version:      {}", self.switch_version);
+        println!("Port:         {}", self.switch_port);
+        println!("Data:         {}", self.switch_vlan_d);
+        println!("Voice:        {}", self.switch_vlan_v);
+        println!(
+            "FDP:          v{} (TTL {}s, Checksum {})",
+            self.version,
+            self.ttl,
+            if self.checksum_valid { "OK" } else { "Invalid" }
+        );
+        println!("Bytes:        {:02X?}", self.bytes);
         println!("");
     }
 
-    /// Remove alphabetic characters from a given string.
-    fn remove_chars(str: &String) -> String {
-        let mut new_str = String::new();
-        for c in str.chars() {
-            if !c.is_alphabetic() {
-                new_str.push(c);
-            }
+    /// Verify the 16 bit one's complement checksum of the FDP PDU (header and TLVs).
+    /// An odd trailing byte is zero padded as in RFC 1071.
+    fn checksum_valid(pdu: &[u8]) -> bool {
+        let mut sum: u32 = 0;
+        for word in pdu.chunks(2) {
+            sum += match word {
+                [high, low] => u16::from_be_bytes([*high, *low]) as u32,
+                [high] => (*high as u32) << 8,
+                _ => 0,
+            };
+        }
+
+        while sum > 0xFFFF {
+            sum = (sum & 0xFFFF) + (sum >> 16);
         }
-        new_str
+        sum == 0xFFFF
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An FDP frame with a device ID TLV, the given TLVs and a valid checksum.
+    fn frame(version: u8, tlvs: &[u8]) -> Vec<u8> {
+        let mut pdu = vec![version, 180, 0x00, 0x00];
+        pdu.extend([0x00, 0x01, 0x00, 0x08]);
+        pdu.extend(b"sw01");
+        pdu.extend(tlvs);
+        let mut sum: u32 = 0;
+        for word in pdu.chunks(2) {
+            sum += u16::from_be_bytes([word[0], word.get(1).copied().unwrap_or(0)]) as u32;
+        }
+        while sum > 0xFFFF {
+            sum = (sum & 0xFFFF) + (sum >> 16);
+        }
+        pdu[2..4].copy_from_slice(&(!(sum as u16)).to_be_bytes());
+
+        let mut bytes = vec![
+            0x01, 0xE0, 0x52, 0xCC, 0xCC, 0xCC, 0x00, 0xE0, 0x52, 0x01, 0x02, 0x03,
+        ];
+        bytes.extend(((SNAP_HEADER.len() + pdu.len()) as u16).to_be_bytes());
+        bytes.extend(SNAP_HEADER);
+        bytes.extend(pdu);
+        bytes
+    }
+
+    #[test]
+    fn parses_header() {
+        let bytes = frame(2, &[]);
+        let pdu = FdpPdu::new(&bytes).unwrap();
+        assert_eq!(pdu.version, 2);
+        assert_eq!(pdu.ttl, 180);
+        assert!(pdu.checksum_valid);
+        assert_eq!(pdu.switch_name, "sw01");
+    }
+
+    #[test]
+    fn rejects_short_length() {
+        let mut bytes = frame(2, &[]);
+        bytes[12..14].copy_from_slice(&4u16.to_be_bytes());
+        assert!(FdpPdu::new(&bytes).is_none());
+    }
+
+    #[test]
+    fn rejects_unknown_version() {
+        assert!(FdpPdu::new(&frame(3, &[])).is_none());
+    }
+
+    #[test]
+    fn parses_tag_info() {
+        let tag_info = [
+            0x01, 0x08, 0x00, 0x0D, 0, 0, 0x00, 0x0A, 0, 0, 0, 0x00, 0x14,
+        ];
+        let bytes = frame(2, &tag_info);
+        let pdu = FdpPdu::new(&bytes).unwrap();
+        assert!(pdu.checksum_valid);
+        assert_eq!(pdu.vlan(), "10, 20");
+    }
+
+    #[test]
+    fn ignores_short_vlan_tlvs() {
+        let tag_info = [0x01, 0x08, 0x00, 0x07, 0, 0, 0x00];
+        let bytes = frame(2, &tag_info);
+        let pdu = FdpPdu::new(&bytes).unwrap();
+        assert_eq!(pdu.vlan(), "");
+
+        let vlan = [0x01, 0x02, 0x00, 0x05, 0x00];
+        let bytes = frame(2, &vlan);
+        let pdu = FdpPdu::new(&bytes).unwrap();
+        assert_eq!(pdu.vlan(), "");
     }
 }
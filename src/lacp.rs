@@ -0,0 +1,167 @@
+use std::fmt;
+
+use crate::util::hex_string;
+
+/// Slow Protocols ethertype shared by LACP, Marker and OAM.
+pub const ETHERTYPE: u16 = 0x8809;
+
+/// Slow Protocols subtype for LACP.
+const SUBTYPE: u8 = 0x01;
+
+#[allow(non_snake_case)]
+pub mod TlvType {
+    pub const TERMINATOR: u8 = 0x00;
+    pub const ACTOR: u8 = 0x01;
+    pub const PARTNER: u8 = 0x02;
+}
+
+#[allow(non_snake_case)]
+pub mod State {
+    pub const ACTIVITY: u8 = 0x01;
+    pub const TIMEOUT: u8 = 0x02;
+    pub const AGGREGATION: u8 = 0x04;
+    pub const SYNCHRONIZATION: u8 = 0x08;
+    pub const COLLECTING: u8 = 0x10;
+    pub const DISTRIBUTING: u8 = 0x20;
+    pub const DEFAULTED: u8 = 0x40;
+    pub const EXPIRED: u8 = 0x80;
+}
+
+/// Actor or partner information of an LACPDU.
+pub struct LacpPort {
+    pub system_priority: u16,
+    pub system: String, // MAC
+    pub key: u16,
+    pub port_priority: u16,
+    pub port: u16,
+    pub state: u8,
+}
+
+impl LacpPort {
+    /// System priority (2), system (6), key (2), port priority (2), port (2), state (1)
+    fn parse(bytes: &[u8]) -> Self {
+        Self {
+            system_priority: u16::from_be_bytes([bytes[0], bytes[1]]),
+            system: hex_string(&bytes[2..8]),
+            key: u16::from_be_bytes([bytes[8], bytes[9]]),
+            port_priority: u16::from_be_bytes([bytes[10], bytes[11]]),
+            port: u16::from_be_bytes([bytes[12], bytes[13]]),
+            state: bytes[14],
+        }
+    }
+
+    /// Names of the state flags that are set.
+    pub fn state_names(&self) -> Vec<&'static str> {
+        const NAMES: [(u8, &str); 8] = [
+            (State::ACTIVITY, "Active"),
+            (State::TIMEOUT, "Short Timeout"),
+            (State::AGGREGATION, "Aggregatable"),
+            (State::SYNCHRONIZATION, "In Sync"),
+            (State::COLLECTING, "Collecting"),
+            (State::DISTRIBUTING, "Distributing"),
+            (State::DEFAULTED, "Defaulted"),
+            (State::EXPIRED, "Expired"),
+        ];
+        NAMES
+            .iter()
+            .filter(|(flag, _)| self.state & flag != 0)
+            .map(|(_, name)| *name)
+            .collect()
+    }
+
+    /// Check if the port is in sync and both collecting and distributing.
+    fn is_aggregated(&self) -> bool {
+        let up = State::SYNCHRONIZATION | State::COLLECTING | State::DISTRIBUTING;
+        self.state & up == up
+    }
+}
+
+impl fmt::Display for LacpPort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{},{} Key {}, Port {},{} ({})",
+            self.system_priority,
+            self.system,
+            self.key,
+            self.port_priority,
+            self.port,
+            self.state_names().join(", ")
+        )
+    }
+}
+
+/// Represents a Link Aggregation Control Protocol PDU received over the
+/// Slow Protocols ethertype.
+pub struct LacpPdu<'a> {
+    bytes: &'a [u8],
+    pub version: u8,
+    pub actor: LacpPort,
+    pub partner: LacpPort,
+}
+
+impl<'a> LacpPdu<'a> {
+    // Attempt to create a new LACP PDU with the given packet.
+    pub fn new(bytes: &'a [u8]) -> Option<Self> {
+        // Ethernet header (14), subtype (1), version (1), actor TLV (20), partner TLV (20)
+        if bytes.len() < 56
+            || u16::from_be_bytes([bytes[12], bytes[13]]) != ETHERTYPE
+            || bytes[14] != SUBTYPE
+        {
+            return None;
+        }
+
+        // Type (1), length (1), the length includes the 2 header bytes.
+        let mut actor = None;
+        let mut partner = None;
+        let mut index = 16;
+        while index + 2 <= bytes.len() {
+            let t = bytes[index];
+            let l = bytes[index + 1] as usize;
+            if t == TlvType::TERMINATOR || l < 2 || (index + l) > bytes.len() {
+                break;
+            }
+            let value = &bytes[index + 2..index + l];
+
+            match t {
+                TlvType::ACTOR if value.len() >= 15 => actor = Some(LacpPort::parse(value)),
+                TlvType::PARTNER if value.len() >= 15 => partner = Some(LacpPort::parse(value)),
+                _ => {}
+            }
+
+            // Go to the next TLV
+            index += l;
+        }
+
+        Some(Self {
+            bytes,
+            version: bytes[15],
+            actor: actor?,
+            partner: partner?,
+        })
+    }
+
+    /// Return the aggregation state of the link as seen from the actor.
+    pub fn aggregation(&self) -> &'static str {
+        if self.actor.state & State::AGGREGATION == 0 {
+            "Individual"
+        } else if self.actor.is_aggregated() && self.partner.is_aggregated() {
+            "Aggregated"
+        } else if self.partner.state & State::DEFAULTED != 0
+            || self.actor.state & State::DEFAULTED != 0
+        {
+            "Defaulted"
+        } else {
+            "Negotiating"
+        }
+    }
+
+    pub fn print(&self) {
+        println!();
+        println!("LACP:    v{} {}", self.version, self.aggregation());
+        println!("Actor:   {}", self.actor);
+        println!("Partner: {}", self.partner);
+        println!("Bytes:   {:02X?}", self.bytes);
+        println!();
+    }
+}
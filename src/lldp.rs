@@ -1,7 +1,25 @@
-use std::str::from_utf8;
+use std::{
+    fmt,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    str::from_utf8,
+};
 
 use to_binary::BinaryString;
 
+use crate::util::{hex_string, remove_chars};
+
+/// LLDP ethertype, checked since two of the group addresses are shared with
+/// other protocols.
+pub const ETHERTYPE: u16 = 0x88CC;
+
+/// Group addresses that set how far an LLDPDU travels before it is consumed.
+#[allow(non_snake_case)]
+pub mod GroupAddress {
+    pub const NEAREST_BRIDGE: [u8; 6] = [0x01, 0x80, 0xC2, 0x00, 0x00, 0x0E];
+    pub const NEAREST_NON_TPMR_BRIDGE: [u8; 6] = [0x01, 0x80, 0xC2, 0x00, 0x00, 0x03];
+    pub const NEAREST_CUSTOMER_BRIDGE: [u8; 6] = [0x01, 0x80, 0xC2, 0x00, 0x00, 0x00];
+}
+
 #[allow(non_snake_case)]
 pub mod TlvType {
     pub const END_OF_LLDP_PDU: usize = 0x00;
@@ -16,6 +34,786 @@ pub mod TlvType {
     pub const VLAN_ID: usize = 0x7F;
 }
 
+#[allow(non_snake_case)]
+pub mod ChassisIdSubtype {
+    pub const CHASSIS_COMPONENT: u8 = 0x01;
+    pub const INTERFACE_ALIAS: u8 = 0x02;
+    pub const PORT_COMPONENT: u8 = 0x03;
+    pub const MAC_ADDRESS: u8 = 0x04;
+    pub const NETWORK_ADDRESS: u8 = 0x05;
+    pub const INTERFACE_NAME: u8 = 0x06;
+    pub const LOCALLY_ASSIGNED: u8 = 0x07;
+}
+
+#[allow(non_snake_case)]
+pub mod PortIdSubtype {
+    pub const INTERFACE_ALIAS: u8 = 0x01;
+    pub const PORT_COMPONENT: u8 = 0x02;
+    pub const MAC_ADDRESS: u8 = 0x03;
+    pub const NETWORK_ADDRESS: u8 = 0x04;
+    pub const INTERFACE_NAME: u8 = 0x05;
+    pub const AGENT_CIRCUIT_ID: u8 = 0x06;
+    pub const LOCALLY_ASSIGNED: u8 = 0x07;
+}
+
+/// IANA address family numbers.
+#[allow(non_snake_case)]
+pub mod AddressFamily {
+    pub const IPV4: u8 = 0x01;
+    pub const IPV6: u8 = 0x02;
+    pub const MAC: u8 = 0x06;
+}
+
+#[allow(non_snake_case)]
+pub mod Capability {
+    pub const OTHER: u16 = 0x0001;
+    pub const REPEATER: u16 = 0x0002;
+    pub const BRIDGE: u16 = 0x0004;
+    pub const WLAN_AP: u16 = 0x0008;
+    pub const ROUTER: u16 = 0x0010;
+    pub const TELEPHONE: u16 = 0x0020;
+    pub const DOCSIS: u16 = 0x0040;
+    pub const STATION: u16 = 0x0080;
+    pub const C_VLAN: u16 = 0x0100;
+    pub const S_VLAN: u16 = 0x0200;
+    pub const TPMR: u16 = 0x0400;
+}
+
+#[allow(non_snake_case)]
+pub mod Oui {
+    pub const IEEE_802_1: [u8; 3] = [0x00, 0x80, 0xC2];
+    pub const IEEE_802_3: [u8; 3] = [0x00, 0x12, 0x0F];
+    pub const TIA_MED: [u8; 3] = [0x00, 0x12, 0xBB];
+}
+
+#[allow(non_snake_case)]
+pub mod Dot1Subtype {
+    pub const PORT_VLAN_ID: u8 = 0x01;
+    pub const PORT_AND_PROTOCOL_VLAN_ID: u8 = 0x02;
+    pub const VLAN_NAME: u8 = 0x03;
+    pub const PROTOCOL_IDENTITY: u8 = 0x04;
+    pub const VID_USAGE_DIGEST: u8 = 0x05;
+    pub const MANAGEMENT_VID: u8 = 0x06;
+    pub const LINK_AGGREGATION: u8 = 0x07;
+}
+
+#[allow(non_snake_case)]
+pub mod Dot3Subtype {
+    pub const MAC_PHY_CONFIG_STATUS: u8 = 0x01;
+    pub const POWER_VIA_MDI: u8 = 0x02;
+    pub const LINK_AGGREGATION: u8 = 0x03;
+    pub const MAXIMUM_FRAME_SIZE: u8 = 0x04;
+}
+
+#[allow(non_snake_case)]
+pub mod MedSubtype {
+    pub const CAPABILITIES: u8 = 0x01;
+    pub const NETWORK_POLICY: u8 = 0x02;
+    pub const LOCATION_ID: u8 = 0x03;
+    pub const EXTENDED_POWER: u8 = 0x04;
+    pub const HARDWARE_REVISION: u8 = 0x05;
+    pub const FIRMWARE_REVISION: u8 = 0x06;
+    pub const SOFTWARE_REVISION: u8 = 0x07;
+    pub const SERIAL_NUMBER: u8 = 0x08;
+    pub const MANUFACTURER_NAME: u8 = 0x09;
+    pub const MODEL_NAME: u8 = 0x0A;
+    pub const ASSET_ID: u8 = 0x0B;
+}
+
+#[allow(non_snake_case)]
+pub mod LocationFormat {
+    pub const COORDINATE: u8 = 0x01;
+    pub const CIVIC: u8 = 0x02;
+    pub const ELIN: u8 = 0x03;
+}
+
+#[allow(non_snake_case)]
+pub mod MedApplication {
+    pub const VOICE: u8 = 0x01;
+    pub const VOICE_SIGNALING: u8 = 0x02;
+    pub const GUEST_VOICE: u8 = 0x03;
+    pub const GUEST_VOICE_SIGNALING: u8 = 0x04;
+    pub const SOFTPHONE_VOICE: u8 = 0x05;
+    pub const VIDEO_CONFERENCING: u8 = 0x06;
+    pub const STREAMING_VIDEO: u8 = 0x07;
+    pub const VIDEO_SIGNALING: u8 = 0x08;
+}
+
+/// IEEE 802.1 Port and Protocol VLAN ID TLV.
+pub struct ProtocolVlan {
+    pub supported: bool,
+    pub enabled: bool,
+    pub vlan_id: u16,
+}
+
+impl fmt::Display for ProtocolVlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} (Supported: {}, Enabled: {})",
+            self.vlan_id, self.supported, self.enabled
+        )
+    }
+}
+
+/// IEEE 802.1 VLAN Name TLV, sent once for each VLAN on the port.
+pub struct VlanName {
+    pub vlan_id: u16,
+    pub name: String,
+}
+
+impl fmt::Display for VlanName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.vlan_id, self.name)
+    }
+}
+
+/// IEEE 802.1 (or legacy IEEE 802.3) Link Aggregation TLV.
+pub struct LinkAggregation {
+    pub capable: bool,
+    pub enabled: bool,
+    pub port_id: u32, // Aggregated port ID, 0 if not aggregated
+}
+
+impl LinkAggregation {
+    // Aggregation status (1), aggregated port ID (4)
+    fn parse(value: &[u8]) -> Option<Self> {
+        if value.len() < 5 {
+            return None;
+        }
+        Some(Self {
+            capable: value[0] & 0x01 != 0,
+            enabled: value[0] & 0x02 != 0,
+            port_id: u32::from_be_bytes([value[1], value[2], value[3], value[4]]),
+        })
+    }
+}
+
+impl fmt::Display for LinkAggregation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Capable: {}, Enabled: {}, Port ID: {}",
+            self.capable, self.enabled, self.port_id
+        )
+    }
+}
+
+/// IEEE 802.3 MAC/PHY Configuration/Status TLV.
+pub struct MacPhy {
+    pub autoneg_supported: bool,
+    pub autoneg_enabled: bool,
+    pub advertised: u16, // PMD auto-negotiation advertised capability
+    pub mau_type: u16,   // Operational MAU type (RFC 4836)
+}
+
+impl MacPhy {
+    /// Names of the advertised auto-negotiation capabilities.
+    pub fn advertised_names(&self) -> Vec<&'static str> {
+        // Bit 0 is the most significant bit.
+        const NAMES: [&str; 16] = [
+            "Other",
+            "10BASE-T Half",
+            "10BASE-T Full",
+            "100BASE-T4",
+            "100BASE-TX Half",
+            "100BASE-TX Full",
+            "100BASE-T2 Half",
+            "100BASE-T2 Full",
+            "PAUSE",
+            "Asymmetric PAUSE",
+            "Symmetric PAUSE",
+            "Asymmetric and Symmetric PAUSE",
+            "1000BASE-X Half",
+            "1000BASE-X Full",
+            "1000BASE-T Half",
+            "1000BASE-T Full",
+        ];
+        NAMES
+            .iter()
+            .enumerate()
+            .filter(|(bit, _)| self.advertised & (0x8000 >> bit) != 0)
+            .map(|(_, name)| *name)
+            .collect()
+    }
+
+    /// Name, speed and duplex of the operational MAU type.
+    pub fn mau(&self) -> Option<(&'static str, &'static str, &'static str)> {
+        let mau = match self.mau_type {
+            1 => ("AUI", "10 Mb/s", "Unknown"),
+            2 => ("10BASE5", "10 Mb/s", "Half"),
+            3 => ("FOIRL", "10 Mb/s", "Half"),
+            4 => ("10BASE2", "10 Mb/s", "Half"),
+            5 => ("10BASE-T", "10 Mb/s", "Unknown"),
+            6 => ("10BASE-FP", "10 Mb/s", "Half"),
+            7 => ("10BASE-FB", "10 Mb/s", "Half"),
+            8 => ("10BASE-FL", "10 Mb/s", "Unknown"),
+            9 => ("10BROAD36", "10 Mb/s", "Half"),
+            10 => ("10BASE-T", "10 Mb/s", "Half"),
+            11 => ("10BASE-T", "10 Mb/s", "Full"),
+            12 => ("10BASE-FL", "10 Mb/s", "Half"),
+            13 => ("10BASE-FL", "10 Mb/s", "Full"),
+            14 => ("100BASE-T4", "100 Mb/s", "Half"),
+            15 => ("100BASE-TX", "100 Mb/s", "Half"),
+            16 => ("100BASE-TX", "100 Mb/s", "Full"),
+            17 => ("100BASE-FX", "100 Mb/s", "Half"),
+            18 => ("100BASE-FX", "100 Mb/s", "Full"),
+            19 => ("100BASE-T2", "100 Mb/s", "Half"),
+            20 => ("100BASE-T2", "100 Mb/s", "Full"),
+            21 => ("1000BASE-X", "1 Gb/s", "Half"),
+            22 => ("1000BASE-X", "1 Gb/s", "Full"),
+            23 => ("1000BASE-LX", "1 Gb/s", "Half"),
+            24 => ("1000BASE-LX", "1 Gb/s", "Full"),
+            25 => ("1000BASE-SX", "1 Gb/s", "Half"),
+            26 => ("1000BASE-SX", "1 Gb/s", "Full"),
+            27 => ("1000BASE-CX", "1 Gb/s", "Half"),
+            28 => ("1000BASE-CX", "1 Gb/s", "Full"),
+            29 => ("1000BASE-T", "1 Gb/s", "Half"),
+            30 => ("1000BASE-T", "1 Gb/s", "Full"),
+            31 => ("10GBASE-X", "10 Gb/s", "Full"),
+            32 => ("10GBASE-LX4", "10 Gb/s", "Full"),
+            33 => ("10GBASE-R", "10 Gb/s", "Full"),
+            34 => ("10GBASE-ER", "10 Gb/s", "Full"),
+            35 => ("10GBASE-LR", "10 Gb/s", "Full"),
+            36 => ("10GBASE-SR", "10 Gb/s", "Full"),
+            37 => ("10GBASE-W", "10 Gb/s", "Full"),
+            38 => ("10GBASE-EW", "10 Gb/s", "Full"),
+            39 => ("10GBASE-LW", "10 Gb/s", "Full"),
+            40 => ("10GBASE-SW", "10 Gb/s", "Full"),
+            41 => ("10GBASE-CX4", "10 Gb/s", "Full"),
+            42 => ("2BASE-TL", "2 Mb/s", "Full"),
+            43 => ("10PASS-TS", "10 Mb/s", "Full"),
+            44 => ("100BASE-BX10D", "100 Mb/s", "Full"),
+            45 => ("100BASE-BX10U", "100 Mb/s", "Full"),
+            46 => ("100BASE-LX10", "100 Mb/s", "Full"),
+            47 => ("1000BASE-BX10D", "1 Gb/s", "Full"),
+            48 => ("1000BASE-BX10U", "1 Gb/s", "Full"),
+            49 => ("1000BASE-LX10", "1 Gb/s", "Full"),
+            50 => ("1000BASE-PX10D", "1 Gb/s", "Full"),
+            51 => ("1000BASE-PX10U", "1 Gb/s", "Full"),
+            52 => ("1000BASE-PX20D", "1 Gb/s", "Full"),
+            53 => ("1000BASE-PX20U", "1 Gb/s", "Full"),
+            54 => ("10GBASE-T", "10 Gb/s", "Full"),
+            55 => ("10GBASE-LRM", "10 Gb/s", "Full"),
+            56 => ("1000BASE-KX", "1 Gb/s", "Full"),
+            57 => ("10GBASE-KX4", "10 Gb/s", "Full"),
+            58 => ("10GBASE-KR", "10 Gb/s", "Full"),
+            70 => ("40GBASE-KR4", "40 Gb/s", "Full"),
+            71 => ("40GBASE-CR4", "40 Gb/s", "Full"),
+            72 => ("40GBASE-SR4", "40 Gb/s", "Full"),
+            73 => ("40GBASE-FR", "40 Gb/s", "Full"),
+            74 => ("40GBASE-LR4", "40 Gb/s", "Full"),
+            75 => ("100GBASE-CR10", "100 Gb/s", "Full"),
+            76 => ("100GBASE-SR10", "100 Gb/s", "Full"),
+            77 => ("100GBASE-LR4", "100 Gb/s", "Full"),
+            78 => ("100GBASE-ER4", "100 Gb/s", "Full"),
+            _ => return None,
+        };
+        Some(mau)
+    }
+
+    /// Return the operational speed and duplex, e.g. "1 Gb/s Full Duplex (1000BASE-T)".
+    pub fn speed_duplex(&self) -> String {
+        match self.mau() {
+            Some((name, speed, "Unknown")) => format!("{} ({})", speed, name),
+            Some((name, speed, duplex)) => format!("{} {} Duplex ({})", speed, duplex, name),
+            None => format!("Unknown MAU Type ({})", self.mau_type),
+        }
+    }
+}
+
+impl fmt::Display for MacPhy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}, Auto-Negotiation Supported: {}, Enabled: {}, Advertised: [{}]",
+            self.speed_duplex(),
+            self.autoneg_supported,
+            self.autoneg_enabled,
+            self.advertised_names().join(", ")
+        )
+    }
+}
+
+/// IEEE 802.3 Power via MDI TLV.
+#[allow(dead_code)]
+pub struct PowerViaMdi {
+    pub port_class_pse: bool, // Port class is PSE, otherwise PD
+    pub pse_power_supported: bool,
+    pub pse_power_enabled: bool,
+    pub pse_pairs_control: bool,
+    pub power_pair: u8,         // 1 signal, 2 spare
+    pub power_class: u8,        // 1-5 map to class 0-4
+    pub requested: Option<u16>, // 0.1 W, 802.3at extension
+    pub allocated: Option<u16>, // 0.1 W, 802.3at extension
+}
+
+impl fmt::Display for PowerViaMdi {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}, Supported: {}, Enabled: {}, Class {}",
+            if self.port_class_pse { "PSE" } else { "PD" },
+            self.pse_power_supported,
+            self.pse_power_enabled,
+            self.power_class.saturating_sub(1)
+        )?;
+        if let Some(requested) = self.requested {
+            write!(f, ", Requested {} W", requested as f64 / 10.0)?;
+        }
+        if let Some(allocated) = self.allocated {
+            write!(f, ", Allocated {} W", allocated as f64 / 10.0)?;
+        }
+        Ok(())
+    }
+}
+
+/// LLDP-MED Capabilities TLV.
+pub struct MedCapabilities {
+    pub capabilities: u16,
+    pub device_type: u8,
+}
+
+impl MedCapabilities {
+    /// Names of the LLDP-MED TLV sets the device supports.
+    pub fn names(&self) -> Vec<&'static str> {
+        const NAMES: [&str; 6] = [
+            "Capabilities",
+            "Network Policy",
+            "Location Identification",
+            "Extended Power via MDI-PSE",
+            "Extended Power via MDI-PD",
+            "Inventory",
+        ];
+        NAMES
+            .iter()
+            .enumerate()
+            .filter(|(bit, _)| self.capabilities & (1 << bit) != 0)
+            .map(|(_, name)| *name)
+            .collect()
+    }
+
+    pub fn device_type_name(&self) -> &'static str {
+        match self.device_type {
+            1 => "Endpoint Class I",
+            2 => "Endpoint Class II",
+            3 => "Endpoint Class III",
+            4 => "Network Connectivity",
+            _ => "Not Defined",
+        }
+    }
+}
+
+impl fmt::Display for MedCapabilities {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} [{}]",
+            self.device_type_name(),
+            self.names().join(", ")
+        )
+    }
+}
+
+/// LLDP-MED Network Policy TLV.
+pub struct NetworkPolicy {
+    pub application_type: u8,
+    pub unknown: bool, // Policy is required by the device but currently unknown
+    pub tagged: bool,  // VLAN ID is used, otherwise the traffic is untagged
+    pub vlan_id: u16,
+    pub priority: u8, // 802.1p priority
+    pub dscp: u8,
+}
+
+impl NetworkPolicy {
+    pub fn application_name(&self) -> &'static str {
+        match self.application_type {
+            MedApplication::VOICE => "Voice",
+            MedApplication::VOICE_SIGNALING => "Voice Signaling",
+            MedApplication::GUEST_VOICE => "Guest Voice",
+            MedApplication::GUEST_VOICE_SIGNALING => "Guest Voice Signaling",
+            MedApplication::SOFTPHONE_VOICE => "Softphone Voice",
+            MedApplication::VIDEO_CONFERENCING => "Video Conferencing",
+            MedApplication::STREAMING_VIDEO => "Streaming Video",
+            MedApplication::VIDEO_SIGNALING => "Video Signaling",
+            _ => "Reserved",
+        }
+    }
+}
+
+impl fmt::Display for NetworkPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.unknown {
+            return write!(f, "{}: Unknown", self.application_name());
+        }
+        if self.tagged {
+            write!(
+                f,
+                "{}: VLAN {} Tagged",
+                self.application_name(),
+                self.vlan_id
+            )?;
+        } else {
+            write!(f, "{}: Untagged", self.application_name())?;
+        }
+        write!(f, ", Priority {}, DSCP {}", self.priority, self.dscp)
+    }
+}
+
+/// LLDP-MED Location Identification TLV.
+pub enum Location {
+    Coordinate(CoordinateLocation),
+    Civic(CivicAddress),
+    Elin(String), // Emergency Location Identification Number
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Location::Coordinate(coordinate) => write!(f, "{}", coordinate),
+            Location::Civic(civic) => write!(f, "{}", civic),
+            Location::Elin(elin) => write!(f, "ELIN: {}", elin),
+        }
+    }
+}
+
+/// Coordinate-based LCI as defined by RFC 3825.
+#[allow(dead_code)]
+pub struct CoordinateLocation {
+    pub latitude_resolution: u8,
+    pub latitude: f64, // Degrees
+    pub longitude_resolution: u8,
+    pub longitude: f64, // Degrees
+    pub altitude_type: u8,
+    pub altitude_resolution: u8,
+    pub altitude: f64, // Meters or floors, see altitude_type
+    pub datum: u8,
+}
+
+impl CoordinateLocation {
+    /// Decode the 16 byte LCI.
+    fn parse(lci: &[u8]) -> Option<Self> {
+        let lci: [u8; 16] = lci.get(0..16)?.try_into().ok()?;
+        let bits = u128::from_be_bytes(lci);
+
+        // Latitude and longitude are 34 bit two's complement with 25 fraction bits,
+        // altitude is 30 bit two's complement with 8 fraction bits.
+        let fixed_point = |value: u128, width: u32, fraction: u32| {
+            let value = ((value as i64) << (64 - width)) >> (64 - width);
+            value as f64 / (1u64 << fraction) as f64
+        };
+
+        Some(Self {
+            latitude_resolution: ((bits >> 122) & 0x3F) as u8,
+            latitude: fixed_point((bits >> 88) & 0x3_FFFF_FFFF, 34, 25),
+            longitude_resolution: ((bits >> 82) & 0x3F) as u8,
+            longitude: fixed_point((bits >> 48) & 0x3_FFFF_FFFF, 34, 25),
+            altitude_type: ((bits >> 44) & 0x0F) as u8,
+            altitude_resolution: ((bits >> 38) & 0x3F) as u8,
+            altitude: fixed_point((bits >> 8) & 0x3FFF_FFFF, 30, 8),
+            datum: (bits & 0xFF) as u8,
+        })
+    }
+
+    pub fn datum_name(&self) -> &'static str {
+        match self.datum {
+            1 => "WGS84",
+            2 => "NAD83/NAVD88",
+            3 => "NAD83/MLLW",
+            _ => "Unknown Datum",
+        }
+    }
+}
+
+impl fmt::Display for CoordinateLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Lat {:.6}, Lon {:.6}", self.latitude, self.longitude)?;
+        match self.altitude_type {
+            1 => write!(f, ", Alt {} m", self.altitude)?,
+            2 => write!(f, ", Floor {}", self.altitude)?,
+            _ => {}
+        }
+        write!(f, " ({})", self.datum_name())
+    }
+}
+
+/// Civic address LCI as defined by RFC 4776.
+#[allow(dead_code)]
+pub struct CivicAddress {
+    pub what: u8, // 0 DHCP server, 1 network element, 2 client
+    pub country_code: String,
+    pub elements: Vec<CivicAddressElement>,
+}
+
+impl CivicAddress {
+    /// Decode the civic address LCI, starting with the LCI length.
+    fn parse(lci: &[u8]) -> Option<Self> {
+        let lci_len = *lci.first()? as usize;
+        let lci = lci.get(1..=lci_len)?;
+        if lci.len() < 3 {
+            return None;
+        }
+
+        let mut civic = Self {
+            what: lci[0],
+            country_code: String::from_utf8_lossy(&lci[1..3]).to_string(),
+            elements: Vec::new(),
+        };
+
+        // CAtype (1), CAlength (1), CAvalue (n)
+        let mut index = 3;
+        while index + 2 <= lci.len() {
+            let ca_type = lci[index];
+            let ca_len = lci[index + 1] as usize;
+            if index + 2 + ca_len > lci.len() {
+                break;
+            }
+            civic.elements.push(CivicAddressElement {
+                ca_type,
+                value: String::from_utf8_lossy(&lci[index + 2..index + 2 + ca_len]).to_string(),
+            });
+            index += 2 + ca_len;
+        }
+        Some(civic)
+    }
+}
+
+impl fmt::Display for CivicAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Country: {}", self.country_code)?;
+        for element in &self.elements {
+            write!(f, ", {}: {}", element.ca_type_name(), element.value)?;
+        }
+        Ok(())
+    }
+}
+
+/// A single CAtype/CAvalue pair of a civic address.
+pub struct CivicAddressElement {
+    pub ca_type: u8,
+    pub value: String,
+}
+
+impl CivicAddressElement {
+    pub fn ca_type_name(&self) -> &'static str {
+        match self.ca_type {
+            0 => "Language",
+            1 => "State",
+            2 => "County",
+            3 => "City",
+            4 => "City Division",
+            5 => "Neighborhood",
+            6 => "Street Group",
+            16 => "Leading Street Direction",
+            17 => "Trailing Street Suffix",
+            18 => "Street Suffix",
+            19 => "House Number",
+            20 => "House Number Suffix",
+            21 => "Landmark",
+            22 => "Additional Location",
+            23 => "Name",
+            24 => "Postal Code",
+            25 => "Building",
+            26 => "Unit",
+            27 => "Floor",
+            28 => "Room",
+            29 => "Place Type",
+            30 => "Postal Community Name",
+            31 => "PO Box",
+            32 => "Additional Code",
+            33 => "Seat",
+            34 => "Primary Road",
+            35 => "Road Section",
+            36 => "Road Branch",
+            37 => "Road Sub-Branch",
+            38 => "Road Pre-Modifier",
+            39 => "Road Post-Modifier",
+            128 => "Script",
+            _ => "Unknown",
+        }
+    }
+}
+
+/// LLDP-MED Extended Power-via-MDI TLV.
+pub struct MedPower {
+    pub power_type: u8,
+    pub power_source: u8,
+    pub priority: u8,
+    pub value: u16, // 0.1 W
+}
+
+impl MedPower {
+    pub fn power_type_name(&self) -> &'static str {
+        match self.power_type {
+            0 => "PSE Device",
+            1 => "PD Device",
+            _ => "Reserved",
+        }
+    }
+
+    /// The meaning of the power source depends on the power type.
+    pub fn power_source_name(&self) -> &'static str {
+        match (self.power_type, self.power_source) {
+            (_, 0) => "Unknown",
+            (0, 1) => "Primary Power Source",
+            (0, 2) => "Backup Power Source",
+            (1, 1) => "PSE",
+            (1, 2) => "Local",
+            (1, 3) => "PSE and Local",
+            _ => "Reserved",
+        }
+    }
+
+    pub fn priority_name(&self) -> &'static str {
+        match self.priority {
+            1 => "Critical",
+            2 => "High",
+            3 => "Low",
+            _ => "Unknown",
+        }
+    }
+
+    pub fn watts(&self) -> f64 {
+        self.value as f64 / 10.0
+    }
+}
+
+impl fmt::Display for MedPower {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} W ({}, {}, Priority {})",
+            self.watts(),
+            self.power_type_name(),
+            self.power_source_name(),
+            self.priority_name()
+        )
+    }
+}
+
+/// LLDP-MED Inventory TLVs.
+#[derive(Default)]
+pub struct MedInventory {
+    pub hardware_revision: String,
+    pub firmware_revision: String,
+    pub software_revision: String,
+    pub serial_number: String,
+    pub manufacturer: String,
+    pub model: String,
+    pub asset_id: String,
+}
+
+/// System Capabilities TLV.
+pub struct SystemCapabilities {
+    pub system: u16,  // Capabilities the system supports
+    pub enabled: u16, // Capabilities that are currently enabled
+}
+
+impl SystemCapabilities {
+    pub fn system_names(&self) -> Vec<&'static str> {
+        Self::names(self.system)
+    }
+
+    pub fn enabled_names(&self) -> Vec<&'static str> {
+        Self::names(self.enabled)
+    }
+
+    fn names(bits: u16) -> Vec<&'static str> {
+        const NAMES: [(u16, &str); 11] = [
+            (Capability::OTHER, "Other"),
+            (Capability::REPEATER, "Repeater"),
+            (Capability::BRIDGE, "Bridge"),
+            (Capability::WLAN_AP, "WLAN AP"),
+            (Capability::ROUTER, "Router"),
+            (Capability::TELEPHONE, "Telephone"),
+            (Capability::DOCSIS, "DOCSIS"),
+            (Capability::STATION, "Station"),
+            (Capability::C_VLAN, "C-VLAN"),
+            (Capability::S_VLAN, "S-VLAN"),
+            (Capability::TPMR, "TPMR"),
+        ];
+        NAMES
+            .iter()
+            .filter(|(capability, _)| bits & capability != 0)
+            .map(|(_, name)| *name)
+            .collect()
+    }
+}
+
+impl fmt::Display for SystemCapabilities {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} (Enabled: {})",
+            self.system_names().join(", "),
+            self.enabled_names().join(", ")
+        )
+    }
+}
+
+/// Management Address TLV, sent once for each management address.
+#[allow(dead_code)]
+pub struct ManagementAddress {
+    pub family: u8, // IANA address family number
+    pub address: Vec<u8>,
+    pub interface_subtype: u8, // 1 unknown, 2 ifIndex, 3 system port number
+    pub interface_number: u32,
+    pub oid: Vec<u8>,
+}
+
+impl ManagementAddress {
+    // Address string length (1), address subtype (1), address (n),
+    // interface numbering subtype (1), interface number (4), OID string length (1), OID (n)
+    fn parse(value: &[u8]) -> Option<Self> {
+        let address_len = *value.first()? as usize;
+        if address_len < 1 || value.len() < 1 + address_len + 5 {
+            return None;
+        }
+        let family = value[1];
+        let address = value[2..1 + address_len].to_vec();
+        let value = &value[1 + address_len..];
+        let oid = match value.get(5) {
+            Some(&oid_len) => value.get(6..6 + oid_len as usize)?.to_vec(),
+            None => Vec::new(),
+        };
+
+        Some(Self {
+            family,
+            address,
+            interface_subtype: value[0],
+            interface_number: u32::from_be_bytes([value[1], value[2], value[3], value[4]]),
+            oid,
+        })
+    }
+
+    /// The address as an IP address, if it is IPv4 or IPv6.
+    pub fn ip(&self) -> Option<IpAddr> {
+        LldpPdu::ip_address(self.family, &self.address)
+    }
+
+    pub fn interface_subtype_name(&self) -> &'static str {
+        match self.interface_subtype {
+            2 => "ifIndex",
+            3 => "System Port Number",
+            _ => "Unknown",
+        }
+    }
+}
+
+impl fmt::Display for ManagementAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut address = vec![self.family];
+        address.extend(&self.address);
+        write!(
+            f,
+            "{} ({} {})",
+            LldpPdu::network_address(&address),
+            self.interface_subtype_name(),
+            self.interface_number
+        )
+    }
+}
+
 pub struct Tlv {
     pub typ: usize,
     pub len: usize,
@@ -47,15 +845,35 @@ impl Tlv {
 /// Represents a EthernetII layer 2 packet
 #[allow(dead_code)]
 pub struct LldpPdu<'a> {
-    bytes: &'a [u8],                // Raw EthernetII packet bytes
-    valid: bool,                    // Contains valid vlan info?
-    destination_mac: String,        // Port MAC
-    source_mac: String,             // Switch MAC
-    chassis_id: String,             // Switch MAC
-    port_id: String,                // Port MAC
-    pub port_description: String,   // Port Number
-    pub system_description: String, // Switch Name
-    pub vlan: String,               // Vlan
+    bytes: &'a [u8],                                  // Raw EthernetII packet bytes
+    valid: bool,                                      // Contains valid vlan info?
+    destination_mac: String,                          // Port MAC
+    source_mac: String,                               // Switch MAC
+    pub chassis_id_subtype: u8,                       // Kind of chassis identifier
+    pub chassis_id: String,                           // Switch MAC
+    pub port_id_subtype: u8,                          // Kind of port identifier
+    pub port_id: String,                              // Port MAC
+    pub port_description: String,                     // Port Number
+    pub system_description: String,                   // Switch Name
+    pub management_addresses: Vec<ManagementAddress>, // Switch IPs
+    pub system_capabilities: Option<SystemCapabilities>,
+    pub vlan_data: String,  // Port VLAN
+    pub vlan_voice: String, // LLDP-MED voice VLAN
+    pub port_vlan_id: Option<u16>,
+    pub protocol_vlans: Vec<ProtocolVlan>,
+    pub vlan_names: Vec<VlanName>,
+    pub protocol_identities: Vec<Vec<u8>>,
+    pub vid_usage_digest: Option<u32>,
+    pub management_vid: Option<u16>,
+    pub link_aggregation: Option<LinkAggregation>,
+    pub mac_phy: Option<MacPhy>,
+    pub power_via_mdi: Option<PowerViaMdi>,
+    pub max_frame_size: Option<u16>,
+    pub med_capabilities: Option<MedCapabilities>,
+    pub network_policies: Vec<NetworkPolicy>,
+    pub locations: Vec<Location>,
+    pub med_power: Option<MedPower>,
+    pub med_inventory: MedInventory,
     tlvs: Vec<Tlv>,
 }
 
@@ -63,17 +881,40 @@ impl<'a> LldpPdu<'a> {
     // Attempt to create a new LLDP PDU with the given packet.
     pub fn new(bytes: &'a [u8]) -> Option<Self> {
         let bytes = bytes.clone();
+        if bytes.len() < 14 || u16::from_be_bytes([bytes[12], bytes[13]]) != ETHERTYPE {
+            return None;
+        }
 
         let mut pdu = Self {
             bytes,
             valid: false,
             destination_mac: format!("{:02X?}", &bytes[0..=5]),
             source_mac: format!("{:02X?}", &bytes[6..=11]),
+            chassis_id_subtype: 0,
             chassis_id: String::new(),
+            port_id_subtype: 0,
             port_id: String::new(),
             port_description: String::new(),
             system_description: String::new(),
-            vlan: String::new(),
+            management_addresses: Vec::new(),
+            system_capabilities: None,
+            vlan_data: String::new(),
+            vlan_voice: String::new(),
+            port_vlan_id: None,
+            protocol_vlans: Vec::new(),
+            vlan_names: Vec::new(),
+            protocol_identities: Vec::new(),
+            vid_usage_digest: None,
+            management_vid: None,
+            link_aggregation: None,
+            mac_phy: None,
+            power_via_mdi: None,
+            max_frame_size: None,
+            med_capabilities: None,
+            network_policies: Vec::new(),
+            locations: Vec::new(),
+            med_power: None,
+            med_inventory: MedInventory::default(),
             tlvs: Vec::<Tlv>::new(),
         };
 
@@ -83,7 +924,7 @@ impl<'a> LldpPdu<'a> {
             if (index + 1) < bytes.len() {
                 let typ_len_bytes = &bytes[index..=index + 1];
                 let (typ, len) = Self::parse_typ_len(typ_len_bytes);
-                if len > (bytes.len() - index) {
+                if (index + 2 + len) > bytes.len() {
                     println!("Value length error...");
                     break;
                 }
@@ -103,12 +944,178 @@ impl<'a> LldpPdu<'a> {
         }
     }
 
+    /// Return the scope of the group address the PDU was sent to. Only the
+    /// nearest bridge scope is guaranteed to come from the access switch.
+    pub fn scope(&self) -> &'static str {
+        let destination = &self.bytes[0..6];
+        if destination == GroupAddress::NEAREST_BRIDGE {
+            "Nearest Bridge"
+        } else if destination == GroupAddress::NEAREST_NON_TPMR_BRIDGE {
+            "Nearest Non-TPMR Bridge"
+        } else if destination == GroupAddress::NEAREST_CUSTOMER_BRIDGE {
+            "Nearest Customer Bridge"
+        } else {
+            "Unknown"
+        }
+    }
+
+    /// Return the first management IP of the switch, preferring IPv4.
+    pub fn switch_ip(&self) -> String {
+        let ips: Vec<IpAddr> = self
+            .management_addresses
+            .iter()
+            .filter_map(|management_address| management_address.ip())
+            .collect();
+        match ips.iter().find(|ip| ip.is_ipv4()).or_else(|| ips.first()) {
+            Some(ip) => ip.to_string(),
+            None => String::new(),
+        }
+    }
+
+    pub fn switch(&self) -> String {
+        let mut switch = String::new();
+        if !self.system_description.is_empty() {
+            switch.push_str(&self.system_description);
+        }
+        let switch_ip = self.switch_ip();
+        if !switch_ip.is_empty() {
+            if !switch.is_empty() {
+                switch.push(' ');
+            }
+            switch.push_str(&format!("({})", switch_ip));
+        }
+        switch
+    }
+
+    pub fn switch_display(&self) -> String {
+        self.system_description.to_string()
+    }
+
+    /// Return the enabled capabilities of the switch, e.g. "Bridge, Router".
+    pub fn capabilities(&self) -> String {
+        match &self.system_capabilities {
+            Some(capabilities) => capabilities.enabled_names().join(", "),
+            None => String::new(),
+        }
+    }
+
+    pub fn chassis_id_subtype_name(&self) -> &'static str {
+        match self.chassis_id_subtype {
+            ChassisIdSubtype::CHASSIS_COMPONENT => "Chassis Component",
+            ChassisIdSubtype::INTERFACE_ALIAS => "Interface Alias",
+            ChassisIdSubtype::PORT_COMPONENT => "Port Component",
+            ChassisIdSubtype::MAC_ADDRESS => "MAC Address",
+            ChassisIdSubtype::NETWORK_ADDRESS => "Network Address",
+            ChassisIdSubtype::INTERFACE_NAME => "Interface Name",
+            ChassisIdSubtype::LOCALLY_ASSIGNED => "Locally Assigned",
+            _ => "Reserved",
+        }
+    }
+
+    pub fn port_id_subtype_name(&self) -> &'static str {
+        match self.port_id_subtype {
+            PortIdSubtype::INTERFACE_ALIAS => "Interface Alias",
+            PortIdSubtype::PORT_COMPONENT => "Port Component",
+            PortIdSubtype::MAC_ADDRESS => "MAC Address",
+            PortIdSubtype::NETWORK_ADDRESS => "Network Address",
+            PortIdSubtype::INTERFACE_NAME => "Interface Name",
+            PortIdSubtype::AGENT_CIRCUIT_ID => "Agent Circuit ID",
+            PortIdSubtype::LOCALLY_ASSIGNED => "Locally Assigned",
+            _ => "Reserved",
+        }
+    }
+
+    /// Return the VLANs in a String separated by a comma if there is
+    /// more than one.
+    pub fn vlan(&self) -> String {
+        let mut vlan = String::new();
+        if !self.vlan_data.is_empty() {
+            vlan.push_str(&self.vlan_data);
+        }
+        if !self.vlan_voice.is_empty() {
+            if !vlan.is_empty() {
+                vlan.push_str(", ");
+            }
+            vlan.push_str(&self.vlan_voice);
+        }
+        vlan
+    }
+
+    /// Return the VLAN names in a String separated by a comma if there is
+    /// more than one.
+    pub fn vlan_names(&self) -> String {
+        self.vlan_names
+            .iter()
+            .map(|vlan_name| vlan_name.to_string())
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+
+    /// Return the operational speed and duplex of the link.
+    pub fn link(&self) -> String {
+        match &self.mac_phy {
+            Some(mac_phy) => mac_phy.speed_duplex(),
+            None => String::new(),
+        }
+    }
+
+    /// Return the maximum frame size of the link.
+    pub fn max_frame_size(&self) -> String {
+        match self.max_frame_size {
+            Some(max_frame_size) => max_frame_size.to_string(),
+            None => String::new(),
+        }
+    }
+
+    /// Return the locations in a String separated by a semicolon if there is
+    /// more than one.
+    pub fn location(&self) -> String {
+        self.locations
+            .iter()
+            .map(|location| location.to_string())
+            .collect::<Vec<String>>()
+            .join("; ")
+    }
+
+    /// Return the LLDP-MED manufacturer and model name.
+    pub fn model(&self) -> String {
+        let inventory = &self.med_inventory;
+        let mut model = String::new();
+        if !inventory.manufacturer.is_empty() {
+            model.push_str(&inventory.manufacturer);
+        }
+        if !inventory.model.is_empty() {
+            if !model.is_empty() {
+                model.push(' ');
+            }
+            model.push_str(&inventory.model);
+        }
+        model
+    }
+
+    /// Return the LLDP-MED PoE allocation.
+    pub fn power(&self) -> String {
+        match &self.med_power {
+            Some(power) => power.to_string(),
+            None => String::new(),
+        }
+    }
+
     pub fn print(&self) {
         println!("");
         println!("Hex:    {:02X?}", self.bytes);
-        println!("Switch: {} {}", self.system_description, self.chassis_id);
+        println!("Switch: {} {}", self.switch(), self.chassis_id);
         println!("Port:   {} {}", self.port_description, self.port_id);
-        println!("Vlan:   {}", self.vlan);
+        println!("Scope:  {}", self.scope());
+        println!("Caps:   {}", self.capabilities());
+        println!("Data:   {}", self.vlan_data);
+        println!("Voice:  {}", self.vlan_voice);
+        println!("Names:  {}", self.vlan_names());
+        println!("Link:   {}", self.link());
+        println!("MTU:    {}", self.max_frame_size());
+        println!("Model:  {}", self.model());
+        println!("Serial: {}", self.med_inventory.serial_number);
+        println!("PoE:    {}", self.power());
         println!("");
         self.print_tlvs();
         println!("");
@@ -150,21 +1157,32 @@ impl<'a> LldpPdu<'a> {
                 let value = String::from("End of PDU");
                 return Tlv::new(typ, len, value);
             }
-            TlvType::CHASSIS_ID => {
-                let value = format!("{:02X?}", &tlv_bytes[3..]) // 1-2 (typ-len), 3 (sub-type), 4.. (value)
-                    .replace("[", "")
-                    .replace("]", "")
-                    .replace(", ", ":");
-                pdu.chassis_id = value.clone();
-                return Tlv::new(typ, len, value);
+            TlvType::CHASSIS_ID if len >= 1 => {
+                // 1-2 (typ-len), 3 (sub-type), 4.. (value)
+                let subtype = tlv_bytes[2];
+                let id = &tlv_bytes[3..];
+                pdu.chassis_id_subtype = subtype;
+                pdu.chassis_id = match subtype {
+                    ChassisIdSubtype::MAC_ADDRESS => hex_string(id),
+                    ChassisIdSubtype::NETWORK_ADDRESS => Self::network_address(id),
+                    _ => Self::text_or_hex(id),
+                };
+                let value = format!("{} ({})", pdu.chassis_id, pdu.chassis_id_subtype_name());
+                Tlv::new(typ, len, value)
             }
-            TlvType::PORT_ID => {
-                let value = format!("{:02X?}", &tlv_bytes[3..]) // 1-2 (typ-len), 3 (sub-type), 4.. (value)
-                    .replace("[", "")
-                    .replace("]", "")
-                    .replace(", ", ":");
-                pdu.port_id = value.clone();
-                return Tlv::new(typ, len, value);
+            TlvType::PORT_ID if len >= 1 => {
+                // 1-2 (typ-len), 3 (sub-type), 4.. (value)
+                let subtype = tlv_bytes[2];
+                let id = &tlv_bytes[3..];
+                pdu.port_id_subtype = subtype;
+                pdu.port_id = match subtype {
+                    PortIdSubtype::MAC_ADDRESS => hex_string(id),
+                    PortIdSubtype::NETWORK_ADDRESS => Self::network_address(id),
+                    PortIdSubtype::AGENT_CIRCUIT_ID => hex_string(id),
+                    _ => Self::text_or_hex(id),
+                };
+                let value = format!("{} ({})", pdu.port_id, pdu.port_id_subtype_name());
+                Tlv::new(typ, len, value)
             }
             TlvType::TIME_TO_LIVE => Tlv::new(
                 typ,
@@ -173,7 +1191,7 @@ impl<'a> LldpPdu<'a> {
             ),
             TlvType::PORT_DESCRIPTION => {
                 let mut value = from_utf8(&tlv_bytes[2..]).unwrap().to_string();
-                value = Self::remove_chars(&value);
+                value = remove_chars(&value);
                 pdu.port_description = value.clone();
                 Tlv::new(typ, len, value)
             }
@@ -185,32 +1203,36 @@ impl<'a> LldpPdu<'a> {
             TlvType::SYSTEM_DESCRIPTION => {
                 Tlv::new(typ, len, from_utf8(&tlv_bytes[2..]).unwrap().to_string())
             }
-            TlvType::SYSTEM_CAPABILITIES => {
-                let value = format!("{:02X?}", &tlv_bytes[2..]) // 1-2 (typ-len), 3 (sub-type), 4.. (value)
-                    .replace("[", "")
-                    .replace("]", "");
-                return Tlv::new(typ, len, value);
+            TlvType::SYSTEM_CAPABILITIES if len >= 4 => {
+                // 1-2 (typ-len), 3-4 (system capabilities), 5-6 (enabled capabilities)
+                let capabilities = SystemCapabilities {
+                    system: u16::from_be_bytes([tlv_bytes[2], tlv_bytes[3]]),
+                    enabled: u16::from_be_bytes([tlv_bytes[4], tlv_bytes[5]]),
+                };
+                let value = capabilities.to_string();
+                pdu.system_capabilities = Some(capabilities);
+                Tlv::new(typ, len, value)
             }
-            TlvType::MANAGEMENT_ADDRESS => {
-                let value = format!("{:02X?}", &tlv_bytes[2..]) // 1-2 (typ-len), 3 (sub-type), 4.. (value)
-                    .replace("[", "")
-                    .replace("]", "");
-                return Tlv::new(typ, len, value);
+            TlvType::MANAGEMENT_ADDRESS => match ManagementAddress::parse(&tlv_bytes[2..]) {
+                Some(management_address) => {
+                    let value = management_address.to_string();
+                    pdu.management_addresses.push(management_address);
+                    Tlv::new(typ, len, value)
+                }
+                None => Tlv::new(typ, len, String::from("Invalid Management Address")),
+            },
+            TlvType::VLAN_ID if len < 4 => {
+                Tlv::new(typ, len, String::from("Reserved or Custom TLV"))
             }
             TlvType::VLAN_ID => {
-                // Find the VLAN id by using a specific OUI (IEEE defined)
+                // Organizationally specific TLVs are identified by OUI and subtype.
                 let oui = &tlv_bytes[2..=4];
-                if oui == &[0x00, 0x80, 0xC2] && len == 6 {
-                    let mut value =
-                        u16::from_be_bytes(tlv_bytes[6..=7].try_into().unwrap()).to_string();
-
-                    // Make valid=true if there is VLAN info.
-                    if value.len() != 0 {
-                        pdu.valid = true;
-                        value = Self::remove_chars(&value);
-                    }
-                    pdu.vlan = value.clone();
-                    return Tlv::new(typ, len, value);
+                if oui == Oui::IEEE_802_1 {
+                    Self::parse_ieee_802_1(pdu, typ, len, tlv_bytes)
+                } else if oui == Oui::IEEE_802_3 {
+                    Self::parse_ieee_802_3(pdu, typ, len, tlv_bytes)
+                } else if oui == Oui::TIA_MED {
+                    Self::parse_med(pdu, typ, len, tlv_bytes)
                 } else {
                     Tlv::new(typ, len, String::from("Reserved or Custom TLV"))
                 }
@@ -219,13 +1241,385 @@ impl<'a> LldpPdu<'a> {
         }
     }
 
-    fn remove_chars(str: &String) -> String {
-        let mut new_str = String::new();
-        for c in str.chars() {
-            if !c.is_alphabetic() {
-                new_str.push(c);
+    // Parse an IEEE 802.1 organizationally specific TLV.
+    // 1-2 (typ-len), 3-5 (OUI), 6 (sub-type), 7.. (value)
+    fn parse_ieee_802_1(pdu: &mut LldpPdu<'_>, typ: usize, len: usize, tlv_bytes: &[u8]) -> Tlv {
+        let value = &tlv_bytes[6..];
+        match tlv_bytes[5] {
+            Dot1Subtype::PORT_VLAN_ID if value.len() >= 2 => {
+                let vlan_id = u16::from_be_bytes([value[0], value[1]]);
+                let value = vlan_id.to_string();
+
+                // Make valid=true if there is VLAN info.
+                pdu.valid = true;
+                pdu.port_vlan_id = Some(vlan_id);
+                pdu.vlan_data = value.clone();
+                Tlv::new(typ, len, value)
+            }
+            Dot1Subtype::PORT_AND_PROTOCOL_VLAN_ID if value.len() >= 3 => {
+                // Flags (1), PPVID (2)
+                let protocol_vlan = ProtocolVlan {
+                    supported: value[0] & 0x02 != 0,
+                    enabled: value[0] & 0x04 != 0,
+                    vlan_id: u16::from_be_bytes([value[1], value[2]]),
+                };
+                let value = protocol_vlan.to_string();
+                pdu.protocol_vlans.push(protocol_vlan);
+                Tlv::new(typ, len, value)
+            }
+            Dot1Subtype::VLAN_NAME if value.len() >= 3 => {
+                // VID (2), name length (1), name (n)
+                let name_len = (value[2] as usize).min(value.len() - 3);
+                let vlan_name = VlanName {
+                    vlan_id: u16::from_be_bytes([value[0], value[1]]),
+                    name: String::from_utf8_lossy(&value[3..3 + name_len]).to_string(),
+                };
+                let value = vlan_name.to_string();
+                pdu.vlan_names.push(vlan_name);
+                Tlv::new(typ, len, value)
+            }
+            Dot1Subtype::PROTOCOL_IDENTITY if !value.is_empty() => {
+                // Length (1), identity (n)
+                let identity_len = (value[0] as usize).min(value.len() - 1);
+                let identity = value[1..1 + identity_len].to_vec();
+                let value = format!("{:02X?}", identity);
+                pdu.protocol_identities.push(identity);
+                Tlv::new(typ, len, value)
+            }
+            Dot1Subtype::VID_USAGE_DIGEST if value.len() >= 4 => {
+                let digest = u32::from_be_bytes([value[0], value[1], value[2], value[3]]);
+                pdu.vid_usage_digest = Some(digest);
+                Tlv::new(typ, len, format!("{:08X}", digest))
+            }
+            Dot1Subtype::MANAGEMENT_VID if value.len() >= 2 => {
+                let vlan_id = u16::from_be_bytes([value[0], value[1]]);
+                pdu.management_vid = Some(vlan_id);
+                Tlv::new(typ, len, vlan_id.to_string())
+            }
+            Dot1Subtype::LINK_AGGREGATION => match LinkAggregation::parse(value) {
+                Some(link_aggregation) => {
+                    let value = link_aggregation.to_string();
+                    pdu.link_aggregation = Some(link_aggregation);
+                    Tlv::new(typ, len, value)
+                }
+                None => Tlv::new(typ, len, String::from("Invalid Link Aggregation")),
+            },
+            _ => Tlv::new(typ, len, String::from("Reserved or Custom TLV")),
+        }
+    }
+
+    // Parse an IEEE 802.3 organizationally specific TLV.
+    // 1-2 (typ-len), 3-5 (OUI), 6 (sub-type), 7.. (value)
+    fn parse_ieee_802_3(pdu: &mut LldpPdu<'_>, typ: usize, len: usize, tlv_bytes: &[u8]) -> Tlv {
+        let value = &tlv_bytes[6..];
+        match tlv_bytes[5] {
+            Dot3Subtype::MAC_PHY_CONFIG_STATUS if value.len() >= 5 => {
+                // Auto-negotiation support/status (1), advertised capability (2),
+                // operational MAU type (2)
+                let mac_phy = MacPhy {
+                    autoneg_supported: value[0] & 0x01 != 0,
+                    autoneg_enabled: value[0] & 0x02 != 0,
+                    advertised: u16::from_be_bytes([value[1], value[2]]),
+                    mau_type: u16::from_be_bytes([value[3], value[4]]),
+                };
+                let value = mac_phy.to_string();
+                pdu.mac_phy = Some(mac_phy);
+                Tlv::new(typ, len, value)
+            }
+            Dot3Subtype::POWER_VIA_MDI if value.len() >= 3 => {
+                // MDI power support (1), PSE power pair (1), power class (1) and the
+                // optional type/source/priority (1), requested (2), allocated (2)
+                let extended = value.len() >= 8;
+                let power = PowerViaMdi {
+                    port_class_pse: value[0] & 0x01 != 0,
+                    pse_power_supported: value[0] & 0x02 != 0,
+                    pse_power_enabled: value[0] & 0x04 != 0,
+                    pse_pairs_control: value[0] & 0x08 != 0,
+                    power_pair: value[1],
+                    power_class: value[2],
+                    requested: extended.then(|| u16::from_be_bytes([value[4], value[5]])),
+                    allocated: extended.then(|| u16::from_be_bytes([value[6], value[7]])),
+                };
+                let value = power.to_string();
+                pdu.power_via_mdi = Some(power);
+                Tlv::new(typ, len, value)
+            }
+            Dot3Subtype::LINK_AGGREGATION => match LinkAggregation::parse(value) {
+                Some(link_aggregation) => {
+                    let value = link_aggregation.to_string();
+                    pdu.link_aggregation = Some(link_aggregation);
+                    Tlv::new(typ, len, value)
+                }
+                None => Tlv::new(typ, len, String::from("Invalid Link Aggregation")),
+            },
+            Dot3Subtype::MAXIMUM_FRAME_SIZE if value.len() >= 2 => {
+                let max_frame_size = u16::from_be_bytes([value[0], value[1]]);
+                pdu.max_frame_size = Some(max_frame_size);
+                Tlv::new(typ, len, max_frame_size.to_string())
             }
+            _ => Tlv::new(typ, len, String::from("Reserved or Custom TLV")),
         }
-        new_str
+    }
+
+    // Parse an LLDP-MED (TIA-1057) organizationally specific TLV.
+    // 1-2 (typ-len), 3-5 (OUI), 6 (sub-type), 7.. (value)
+    fn parse_med(pdu: &mut LldpPdu<'_>, typ: usize, len: usize, tlv_bytes: &[u8]) -> Tlv {
+        let value = &tlv_bytes[6..];
+        match tlv_bytes[5] {
+            MedSubtype::CAPABILITIES if value.len() >= 3 => {
+                let capabilities = MedCapabilities {
+                    capabilities: u16::from_be_bytes([value[0], value[1]]),
+                    device_type: value[2],
+                };
+                let value = capabilities.to_string();
+                pdu.med_capabilities = Some(capabilities);
+                Tlv::new(typ, len, value)
+            }
+            MedSubtype::NETWORK_POLICY if value.len() >= 4 => {
+                // Application type (8), unknown (1), tagged (1), reserved (1),
+                // VLAN ID (12), L2 priority (3), DSCP (6)
+                let bits = u32::from_be_bytes([0, value[1], value[2], value[3]]);
+                let policy = NetworkPolicy {
+                    application_type: value[0],
+                    unknown: bits & 0x80_0000 != 0,
+                    tagged: bits & 0x40_0000 != 0,
+                    vlan_id: ((bits >> 9) & 0x0FFF) as u16,
+                    priority: ((bits >> 6) & 0x07) as u8,
+                    dscp: (bits & 0x3F) as u8,
+                };
+
+                // Make valid=true if there is voice VLAN info.
+                if policy.application_type == MedApplication::VOICE
+                    && !policy.unknown
+                    && policy.tagged
+                    && policy.vlan_id != 0
+                {
+                    pdu.vlan_voice = policy.vlan_id.to_string();
+                    pdu.valid = true;
+                }
+
+                let value = policy.to_string();
+                pdu.network_policies.push(policy);
+                Tlv::new(typ, len, value)
+            }
+            MedSubtype::LOCATION_ID if !value.is_empty() => {
+                // Location data format (1), location ID (n)
+                let location = match value[0] {
+                    LocationFormat::COORDINATE => {
+                        CoordinateLocation::parse(&value[1..]).map(Location::Coordinate)
+                    }
+                    LocationFormat::CIVIC => CivicAddress::parse(&value[1..]).map(Location::Civic),
+                    LocationFormat::ELIN => Some(Location::Elin(
+                        String::from_utf8_lossy(&value[1..]).to_string(),
+                    )),
+                    _ => None,
+                };
+                match location {
+                    Some(location) => {
+                        let value = location.to_string();
+                        pdu.locations.push(location);
+                        Tlv::new(typ, len, value)
+                    }
+                    None => Tlv::new(typ, len, String::from("Invalid Location")),
+                }
+            }
+            MedSubtype::EXTENDED_POWER if value.len() >= 3 => {
+                // Power type (2), power source (2), power priority (4), power value (16)
+                let power = MedPower {
+                    power_type: value[0] >> 6,
+                    power_source: (value[0] >> 4) & 0x03,
+                    priority: value[0] & 0x0F,
+                    value: u16::from_be_bytes([value[1], value[2]]),
+                };
+                let value = power.to_string();
+                pdu.med_power = Some(power);
+                Tlv::new(typ, len, value)
+            }
+            MedSubtype::HARDWARE_REVISION..=MedSubtype::ASSET_ID => {
+                let inventory = &mut pdu.med_inventory;
+                let field = match tlv_bytes[5] {
+                    MedSubtype::HARDWARE_REVISION => &mut inventory.hardware_revision,
+                    MedSubtype::FIRMWARE_REVISION => &mut inventory.firmware_revision,
+                    MedSubtype::SOFTWARE_REVISION => &mut inventory.software_revision,
+                    MedSubtype::SERIAL_NUMBER => &mut inventory.serial_number,
+                    MedSubtype::MANUFACTURER_NAME => &mut inventory.manufacturer,
+                    MedSubtype::MODEL_NAME => &mut inventory.model,
+                    _ => &mut inventory.asset_id,
+                };
+                *field = String::from_utf8_lossy(value).trim().to_string();
+                Tlv::new(typ, len, field.clone())
+            }
+            _ => Tlv::new(typ, len, String::from("Reserved or Custom TLV")),
+        }
+    }
+
+    /// Format bytes as text if they are printable, otherwise as hex.
+    fn text_or_hex(bytes: &[u8]) -> String {
+        match from_utf8(bytes) {
+            Ok(text) if !text.chars().any(|c| c.is_control()) => text.to_string(),
+            _ => hex_string(bytes),
+        }
+    }
+
+    /// Format an IANA address family number followed by the address.
+    fn network_address(bytes: &[u8]) -> String {
+        match bytes.split_first() {
+            Some((&family, address)) => match Self::ip_address(family, address) {
+                Some(ip) => ip.to_string(),
+                None if family == AddressFamily::MAC => hex_string(address),
+                None => format!("{} (Family {})", hex_string(address), family),
+            },
+            None => String::new(),
+        }
+    }
+
+    /// Decode an address of the given IANA address family as an IP address.
+    fn ip_address(family: u8, address: &[u8]) -> Option<IpAddr> {
+        match family {
+            AddressFamily::IPV4 => <[u8; 4]>::try_from(address)
+                .ok()
+                .map(|octets| IpAddr::V4(Ipv4Addr::from(octets))),
+            AddressFamily::IPV6 => <[u8; 16]>::try_from(address)
+                .ok()
+                .map(|octets| IpAddr::V6(Ipv6Addr::from(octets))),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tlv(typ: u16, value: &[u8]) -> Vec<u8> {
+        let mut bytes = ((typ << 9) | value.len() as u16).to_be_bytes().to_vec();
+        bytes.extend_from_slice(value);
+        bytes
+    }
+
+    /// An LLDP frame with the mandatory TLVs followed by the given TLVs.
+    fn frame(tlvs: &[Vec<u8>]) -> Vec<u8> {
+        let mut bytes = vec![0x01, 0x80, 0xC2, 0x00, 0x00, 0x0E];
+        bytes.extend_from_slice(&[0x00, 0x11, 0x22, 0x33, 0x44, 0x55]);
+        bytes.extend_from_slice(&ETHERTYPE.to_be_bytes());
+        bytes.extend(tlv(1, &[4, 0x00, 0x11, 0x22, 0x33, 0x44, 0x55]));
+        bytes.extend(tlv(2, b"\x051/0/12"));
+        bytes.extend(tlv(3, &[0, 120]));
+        for t in tlvs {
+            bytes.extend(t);
+        }
+        bytes.extend_from_slice(&[0, 0]);
+        bytes
+    }
+
+    /// An LLDP-MED Network Policy TLV. Flags are the unknown and tagged bits.
+    fn network_policy(
+        application_type: u8,
+        flags: u32,
+        vlan_id: u32,
+        priority: u32,
+        dscp: u32,
+    ) -> Vec<u8> {
+        let bits = (flags << 22) | (vlan_id << 9) | (priority << 6) | dscp;
+        let mut value = vec![
+            0x00,
+            0x12,
+            0xBB,
+            MedSubtype::NETWORK_POLICY,
+            application_type,
+        ];
+        value.extend_from_slice(&bits.to_be_bytes()[1..]);
+        tlv(127, &value)
+    }
+
+    #[test]
+    fn decodes_network_policy_fields() {
+        let bytes = frame(&[
+            network_policy(MedApplication::VOICE, 0b01, 100, 5, 46),
+            // All ones next to each field to catch overlapping masks
+            network_policy(MedApplication::VIDEO_CONFERENCING, 0b01, 4095, 7, 63),
+            network_policy(MedApplication::VOICE_SIGNALING, 0b00, 0, 0, 24),
+            network_policy(MedApplication::GUEST_VOICE, 0b10, 0, 0, 0),
+        ]);
+        let pdu = LldpPdu::new(&bytes).unwrap();
+        let policies: Vec<(u8, bool, bool, u16, u8, u8)> = pdu
+            .network_policies
+            .iter()
+            .map(|p| {
+                (
+                    p.application_type,
+                    p.unknown,
+                    p.tagged,
+                    p.vlan_id,
+                    p.priority,
+                    p.dscp,
+                )
+            })
+            .collect();
+        assert_eq!(
+            policies,
+            [
+                (MedApplication::VOICE, false, true, 100, 5, 46),
+                (MedApplication::VIDEO_CONFERENCING, false, true, 4095, 7, 63),
+                (MedApplication::VOICE_SIGNALING, false, false, 0, 0, 24),
+                (MedApplication::GUEST_VOICE, true, false, 0, 0, 0),
+            ]
+        );
+        assert_eq!(pdu.vlan_voice, "100");
+        assert_eq!(
+            pdu.network_policies[0].to_string(),
+            "Voice: VLAN 100 Tagged, Priority 5, DSCP 46"
+        );
+        assert_eq!(pdu.network_policies[3].to_string(), "Guest Voice: Unknown");
+    }
+
+    /// An RFC 3825 LCI with 34 bits of resolution for latitude and longitude.
+    fn lci(latitude: f64, longitude: f64, altitude_type: u8, altitude: f64) -> [u8; 16] {
+        let fixed_point = |value: f64, width: u32, fraction: u32| {
+            ((value * (1u64 << fraction) as f64).round() as i64 as u128) & ((1u128 << width) - 1)
+        };
+        let bits = (34u128 << 122)
+            | (fixed_point(latitude, 34, 25) << 88)
+            | (34u128 << 82)
+            | (fixed_point(longitude, 34, 25) << 48)
+            | ((altitude_type as u128) << 44)
+            | (30u128 << 38)
+            | (fixed_point(altitude, 30, 8) << 8)
+            | 1;
+        bits.to_be_bytes()
+    }
+
+    #[test]
+    fn decodes_negative_coordinates() {
+        let location = CoordinateLocation::parse(&lci(-33.856784, -151.215297, 1, -12.5)).unwrap();
+        assert!((location.latitude + 33.856784).abs() < 1e-7);
+        assert!((location.longitude + 151.215297).abs() < 1e-7);
+        assert_eq!(location.altitude, -12.5);
+        assert_eq!(location.latitude_resolution, 34);
+        assert_eq!(location.datum_name(), "WGS84");
+        assert_eq!(
+            location.to_string(),
+            "Lat -33.856784, Lon -151.215297, Alt -12.5 m (WGS84)"
+        );
+    }
+
+    #[test]
+    fn decodes_positive_coordinates() {
+        let location = CoordinateLocation::parse(&lci(51.477928, 0.001545, 2, 3.0)).unwrap();
+        assert!((location.latitude - 51.477928).abs() < 1e-7);
+        assert!((location.longitude - 0.001545).abs() < 1e-7);
+        assert_eq!(location.altitude, 3.0);
+        assert_eq!(
+            location.to_string(),
+            "Lat 51.477928, Lon 0.001545, Floor 3 (WGS84)"
+        );
+    }
+
+    #[test]
+    fn decodes_coordinate_limits() {
+        let location = CoordinateLocation::parse(&lci(-90.0, 180.0, 0, 0.0)).unwrap();
+        assert_eq!(location.latitude, -90.0);
+        assert_eq!(location.longitude, 180.0);
+        assert!(CoordinateLocation::parse(&[0; 15]).is_none());
     }
 }
@@ -1,22 +1,80 @@
-use std::time::Duration;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    time::{Duration, Instant},
+};
 
+use arp::{ArpPdu, ArpTable};
+use cdp::CdpPdu;
+use cfm::CfmPdu;
+use eapol::EapolPdu;
+use edp::EdpPdu;
 use exit_codes::SUCCESS;
 use fdp::FdpPdu;
+use lacp::LacpPdu;
 use lldp::LldpPdu;
+use mndp::MndpPdu;
+use mvrp::MvrpPdu;
 use nic::Nic;
 use pnet::packet::ethernet::EthernetPacket;
+use ptp::PtpPdu;
+use ra::RaPdu;
 use reg::{print_hklm, Entry};
+use sonmp::SonmpPdu;
+use stp::StpPdu;
+use udld::UdldPdu;
+use vlan::{VlanHistogram, VlanTag};
 
 use crate::exit_codes::REGISTRY_WRITE_FAILURE;
 
+mod arp;
+mod cdp;
+mod cfm;
+mod eapol;
+mod edp;
 mod exit_codes;
 mod fdp;
+mod lacp;
 mod lldp;
+mod mndp;
+mod mvrp;
 mod nic;
+mod ptp;
+mod ra;
 mod reg;
+mod sonmp;
+mod stp;
+mod udld;
+mod util;
+mod vlan;
 
 const REGISTRY_PATH: &'static str = r"SOFTWARE\rport";
 
+/// How long to keep observing the port once a discovery protocol is found, enough
+/// for a few fast LACP, STP, PTP and CFM intervals without waiting out the window.
+const OBSERVE_DURATION: Duration = Duration::from_secs(10);
+
+/// Every value written by a discovery protocol handler. Handlers write different
+/// sets, so the ones not written are removed rather than left from a previous switch.
+const DISCOVERY_KEYS: [&str; 17] = [
+    "LastWrite",
+    "Switch",
+    "SwitchDisplay",
+    "Port",
+    "Vlan",
+    "LldpScope",
+    "VlanNames",
+    "Capabilities",
+    "Link",
+    "MaxFrameSize",
+    "Location",
+    "Model",
+    "PoE",
+    "Platform",
+    "Version",
+    "FdpTtl",
+    "FdpChecksumValid",
+];
+
 fn main() {
     print_hklm(REGISTRY_PATH);
 
@@ -24,85 +82,517 @@ fn main() {
 
     println!("");
 
-    // First try FDP for more details vlan information.
-    print!("FDP | ");
-    nic.listen_wired(Duration::from_secs(62), |bytes| {
+    // The first discovery protocol found and when, written once listening is over.
+    let mut entries: Option<Vec<Entry>> = None;
+    let mut found: Option<Instant> = None;
+
+    // Keep the last LLDPDU from beyond the nearest bridge as a fallback in
+    // case no discovery protocol is found on the access switch.
+    let mut lldp: Option<Vec<u8>> = None;
+
+    // UDLD echoes this host if it runs UDLD, by host name or interface MAC.
+    let mut device_ids = nic.wired_macs();
+    if let Ok(name) = std::env::var("COMPUTERNAME") {
+        device_ids.push(name);
+    }
+    let mut observed = Observed {
+        device_ids,
+        ..Default::default()
+    };
+
+    // First try FDP/CDP/EDP/MNDP/SONMP for more details vlan information.
+    print!("FDP/CDP/EDP/MNDP/SONMP | ");
+    nic.listen_wired(Duration::from_secs(62), |bytes, tags| {
+        observed.observe(bytes, tags);
+        if let Some(found) = found {
+            // Keep observing the port for a while once the switch is known.
+            return found.elapsed() < OBSERVE_DURATION;
+        }
         if let Some(packet) = EthernetPacket::new(bytes) {
-            match packet.get_destination().octets() {
+            entries = match packet.get_destination().octets() {
                 // FDP
-                [0x01, 0xE0, 0x52, 0xCC, 0xCC, 0xCC] => {
-                    println!("Found FDP Packet...");
-                    handle_fdp(bytes);
+                [0x01, 0xE0, 0x52, 0xCC, 0xCC, 0xCC] => handle_fdp(bytes),
+                // CDP, UDLD shares the address and is told apart by the parser.
+                [0x01, 0x00, 0x0C, 0xCC, 0xCC, 0xCC] => handle_cdp(bytes),
+                // EDP
+                [0x00, 0xE0, 0x2B, 0x00, 0x00, 0x00] => handle_edp(bytes),
+                // SONMP
+                [0x01, 0x00, 0x81, 0x00, 0x01, 0x00] | [0x01, 0x00, 0x81, 0x00, 0x01, 0x01] => {
+                    handle_sonmp(bytes)
                 }
-                _ => {}
+                // MNDP is sent over UDP instead of a multicast MAC.
+                _ if MndpPdu::is_mndp(bytes) => handle_mndp(bytes),
+                _ => None,
+            };
+            if entries.is_some() {
+                found = Some(Instant::now());
             }
         }
+        true
     });
 
-    // Try LLDP if FDP doesn't work.
-    print!("LLDP | ");
-    nic.listen_wired(Duration::from_secs(32), |bytes| {
-        if let Some(packet) = EthernetPacket::new(bytes) {
-            match packet.get_destination().octets() {
-                // LLDP
-                [0x01, 0x80, 0xC2, 0x00, 0x00, 0x0E] => {
-                    println!("Found LLDP Packet...");
-                    handle_lldp(bytes);
+    // Try LLDP if FDP/CDP/EDP/MNDP/SONMP doesn't work.
+    if entries.is_none() {
+        print!("LLDP | ");
+        nic.listen_wired(Duration::from_secs(32), |bytes, tags| {
+            observed.observe(bytes, tags);
+            if let Some(found) = found {
+                return found.elapsed() < OBSERVE_DURATION;
+            }
+            if let Some(packet) = EthernetPacket::new(bytes) {
+                entries = match packet.get_destination().octets() {
+                    // LLDP, nearest bridge
+                    [0x01, 0x80, 0xC2, 0x00, 0x00, 0x0E] => handle_lldp(bytes),
+                    // LLDP, nearest non-TPMR bridge and nearest customer bridge
+                    [0x01, 0x80, 0xC2, 0x00, 0x00, 0x03] | [0x01, 0x80, 0xC2, 0x00, 0x00, 0x00]
+                        if LldpPdu::new(bytes).is_some() =>
+                    {
+                        if lldp.is_none() {
+                            println!("Found LLDP Packet beyond the nearest bridge...");
+                        }
+                        lldp = Some(bytes.to_vec());
+                        None
+                    }
+                    _ => None,
+                };
+                if entries.is_some() {
+                    found = Some(Instant::now());
                 }
-                _ => {}
             }
+            true
+        });
+    }
+
+    // Fall back to LLDP from further away, then the spanning tree bridge.
+    if entries.is_none() {
+        if let Some(bytes) = &lldp {
+            println!("Using LLDP Packet beyond the nearest bridge...");
+            entries = handle_lldp(bytes);
+        } else if let Some(bytes) = &observed.bpdu {
+            println!("Using STP BPDU...");
+            entries = handle_stp(bytes);
         }
-    });
+    }
+
+    // What was observed on the port is still written without a discovery
+    // protocol, keeping the last switch and LastWrite as they were.
+    match entries {
+        Some(entries) => {
+            let stale: Vec<&str> = DISCOVERY_KEYS
+                .into_iter()
+                .filter(|key| !entries.iter().any(|entry| entry.key == *key))
+                .collect();
+            write_entries(entries, &stale, &observed)
+        }
+        None if !observed.is_empty() => write_entries(Vec::new(), &[], &observed),
+        None => {}
+    }
 
     println!("");
 }
 
-fn handle_fdp(bytes: &[u8]) {
-    if let Some(pdu) = FdpPdu::new(bytes) {
-        pdu.print();
-        let mut entries = Vec::<Entry>::new();
-        entries.push(Entry::new(
-            "LastWrite",
-            &chrono::offset::Utc::now().to_string(),
-        ));
-        entries.push(Entry::new("Switch", &pdu.switch()));
-        entries.push(Entry::new("SwitchDisplay", &pdu.switch_display()));
-        entries.push(Entry::new("Port", &pdu.switch_port));
-        entries.push(Entry::new("Vlan", &pdu.vlan()));
-
-        match reg::write_hklm(&entries, REGISTRY_PATH) {
-            Ok(_) => {
-                std::process::exit(SUCCESS);
+fn handle_fdp(bytes: &[u8]) -> Option<Vec<Entry>> {
+    let pdu = FdpPdu::new(bytes)?;
+    println!("Found FDP Packet...");
+    pdu.print();
+    Some(vec![
+        Entry::new("LastWrite", &chrono::offset::Utc::now().to_string()),
+        Entry::new("Switch", &pdu.switch()),
+        Entry::new("SwitchDisplay", &pdu.switch_display()),
+        Entry::new("Port", &pdu.switch_port),
+        Entry::new("Vlan", &pdu.vlan()),
+        Entry::new("Capabilities", &pdu.switch_capabilities),
+        Entry::new("Platform", &pdu.switch_platform),
+        Entry::new("Version", &pdu.switch_version),
+        Entry::new("FdpTtl", &pdu.ttl.to_string()),
+        Entry::new("FdpChecksumValid", &pdu.checksum_valid.to_string()),
+    ])
+}
+
+fn handle_cdp(bytes: &[u8]) -> Option<Vec<Entry>> {
+    let pdu = CdpPdu::new(bytes)?;
+    println!("Found CDP Packet...");
+    pdu.print();
+    Some(vec![
+        Entry::new("LastWrite", &chrono::offset::Utc::now().to_string()),
+        Entry::new("Switch", &pdu.switch()),
+        Entry::new("SwitchDisplay", &pdu.switch_display()),
+        Entry::new("Port", &pdu.switch_port),
+        Entry::new("Vlan", &pdu.vlan()),
+    ])
+}
+
+fn handle_edp(bytes: &[u8]) -> Option<Vec<Entry>> {
+    let pdu = EdpPdu::new(bytes)?;
+    println!("Found EDP Packet...");
+    pdu.print();
+    Some(vec![
+        Entry::new("LastWrite", &chrono::offset::Utc::now().to_string()),
+        Entry::new("Switch", &pdu.switch()),
+        Entry::new("SwitchDisplay", &pdu.switch_display()),
+        Entry::new("Port", &pdu.switch_port),
+        Entry::new("Vlan", &pdu.vlan()),
+        Entry::new("VlanNames", &pdu.vlan_names()),
+    ])
+}
+
+fn handle_mndp(bytes: &[u8]) -> Option<Vec<Entry>> {
+    let pdu = MndpPdu::new(bytes)?;
+    println!("Found MNDP Packet...");
+    pdu.print();
+    Some(vec![
+        Entry::new("LastWrite", &chrono::offset::Utc::now().to_string()),
+        Entry::new("Switch", &pdu.switch()),
+        Entry::new("SwitchDisplay", &pdu.switch_display()),
+        Entry::new("Port", &pdu.switch_port),
+        Entry::new("Vlan", &String::new()),
+        Entry::new("Platform", &pdu.platform()),
+        Entry::new("Version", &pdu.switch_version),
+    ])
+}
+
+fn handle_sonmp(bytes: &[u8]) -> Option<Vec<Entry>> {
+    let pdu = SonmpPdu::new(bytes)?;
+    println!("Found SONMP Packet...");
+    pdu.print();
+    Some(vec![
+        Entry::new("LastWrite", &chrono::offset::Utc::now().to_string()),
+        Entry::new("Switch", &pdu.switch()),
+        Entry::new("SwitchDisplay", &pdu.switch_display()),
+        Entry::new("Port", &pdu.switch_port),
+        Entry::new("Vlan", &String::new()),
+    ])
+}
+
+fn handle_lldp(bytes: &[u8]) -> Option<Vec<Entry>> {
+    let pdu = LldpPdu::new(bytes)?;
+    println!("Found LLDP Packet...");
+    pdu.print();
+    Some(vec![
+        Entry::new("LastWrite", &chrono::offset::Utc::now().to_string()),
+        Entry::new("Switch", &pdu.switch()),
+        Entry::new("SwitchDisplay", &pdu.switch_display()),
+        Entry::new("Port", &pdu.port_description),
+        Entry::new("LldpScope", &pdu.scope().to_string()),
+        Entry::new("Vlan", &pdu.vlan()),
+        Entry::new("VlanNames", &pdu.vlan_names()),
+        Entry::new("Capabilities", &pdu.capabilities()),
+        Entry::new("Link", &pdu.link()),
+        Entry::new("MaxFrameSize", &pdu.max_frame_size()),
+        Entry::new("Location", &pdu.location()),
+        Entry::new("Model", &pdu.model()),
+        Entry::new("PoE", &pdu.power()),
+    ])
+}
+
+fn handle_stp(bytes: &[u8]) -> Option<Vec<Entry>> {
+    let pdu = StpPdu::new(bytes)?;
+    pdu.print();
+    Some(vec![
+        Entry::new("LastWrite", &chrono::offset::Utc::now().to_string()),
+        Entry::new("Switch", &pdu.switch()),
+        Entry::new("SwitchDisplay", &pdu.switch_display()),
+        Entry::new("Port", &pdu.switch_port()),
+        Entry::new("Vlan", &pdu.vlan()),
+    ])
+}
+
+/// Frames seen while listening that describe the port rather than the switch.
+/// They are collected over both listen windows and written along with
+/// whichever discovery protocol is found.
+#[derive(Default)]
+struct Observed {
+    bpdu: Option<Vec<u8>>, // Fallback if no discovery protocol is found
+    lacp: Option<Vec<u8>>,
+    udld: Option<Vec<u8>>,
+    mvrp: Option<Vec<u8>>,
+    mvrp_vlans: BTreeSet<u16>,  // Declared over the whole listen window
+    cfm: BTreeMap<u8, Vec<u8>>, // Last CCM for each MD level
+    ptp: Option<Vec<u8>>,
+    eapol: Option<Vec<u8>>,
+    arp: ArpTable,
+    ra: Option<Vec<u8>>,
+    vlans: VlanHistogram,
+    device_ids: Vec<String>, // Identifies this host in UDLD echoes and its own frames
+}
+
+impl Observed {
+    /// Count VLAN tags and keep the port information carried by every packet.
+    fn observe(&mut self, bytes: &[u8], tags: &[VlanTag]) {
+        self.vlans.add(tags);
+        if let Some(packet) = EthernetPacket::new(bytes) {
+            match packet.get_destination().octets() {
+                // UDLD shares the CDP address.
+                [0x01, 0x00, 0x0C, 0xCC, 0xCC, 0xCC] if UdldPdu::new(bytes).is_some() => {
+                    if self.udld.is_none() {
+                        println!("Found UDLD Packet...");
+                    }
+                    self.udld = Some(bytes.to_vec());
+                }
+                // STP/RSTP/MSTP and PVST+
+                [0x01, 0x80, 0xC2, 0x00, 0x00, 0x00] | [0x01, 0x00, 0x0C, 0xCC, 0xCC, 0xCD]
+                    if StpPdu::new(bytes).is_some() =>
+                {
+                    self.bpdu = Some(bytes.to_vec());
+                }
+                // MVRP/GVRP
+                [0x01, 0x80, 0xC2, 0x00, 0x00, 0x21] => {
+                    if let Some(pdu) = MvrpPdu::new(bytes) {
+                        if self.mvrp.is_none() {
+                            println!("Found MVRP Packet...");
+                        }
+                        pdu.update(&mut self.mvrp_vlans);
+                        self.mvrp = Some(bytes.to_vec());
+                    }
+                }
+                // LACP, a teamed NIC sends its own LACPDUs which are captured too.
+                [0x01, 0x80, 0xC2, 0x00, 0x00, 0x02]
+                    if !self.is_own(&packet) && LacpPdu::new(bytes).is_some() =>
+                {
+                    if self.lacp.is_none() {
+                        println!("Found LACP Packet...");
+                    }
+                    self.lacp = Some(bytes.to_vec());
+                }
+                // PTP, the peer delay address is shared with LLDP.
+                [0x01, 0x1B, 0x19, 0x00, 0x00, 0x00] | [0x01, 0x80, 0xC2, 0x00, 0x00, 0x0E] => {
+                    if let Some(pdu) = PtpPdu::new(bytes) {
+                        // Announce messages carry the grandmaster, prefer them over Sync.
+                        let stored = self.ptp.as_deref().and_then(PtpPdu::new);
+                        if stored.is_none() {
+                            println!("Found PTP Packet...");
+                        }
+                        if pdu.is_announce() || !stored.is_some_and(|pdu| pdu.is_announce()) {
+                            self.ptp = Some(bytes.to_vec());
+                        }
+                    }
+                }
+                // 802.1X, usually sent to 01:80:C2:00:00:03. Only the switch's frames
+                // show it is enforcing 802.1X, this host's supplicant is captured too.
+                _ if EapolPdu::new(bytes).is_some_and(|pdu| pdu.is_from_authenticator()) => {
+                    // Identity requests show the switch is enforcing 802.1X, prefer them.
+                    let stored = self.eapol.as_deref().and_then(EapolPdu::new);
+                    if stored.is_none() {
+                        println!("Found EAPOL Packet...");
+                    }
+                    if !stored.is_some_and(|pdu| pdu.is_identity_request()) {
+                        self.eapol = Some(bytes.to_vec());
+                    }
+                }
+                // CFM is sent to a different address for each MD level.
+                _ if CfmPdu::new(bytes).is_some() => {
+                    if self.cfm.is_empty() {
+                        println!("Found CFM Packet...");
+                    }
+                    // Keyed by the MD level in the top 3 bits of the CFM header
+                    self.cfm.insert(bytes[14] >> 5, bytes.to_vec());
+                }
+                _ => self.observe_gateway(bytes),
             }
-            Err(e) => {
-                println!("Failure writing to registry \"{}\"\n{}", REGISTRY_PATH, e);
-                std::process::exit(REGISTRY_WRITE_FAILURE);
+        }
+    }
+
+    /// Check if the frame was sent from one of this host's interfaces.
+    fn is_own(&self, packet: &EthernetPacket) -> bool {
+        let source = packet.get_source().to_string();
+        self.device_ids
+            .iter()
+            .any(|id| id.eq_ignore_ascii_case(&source))
+    }
+
+    /// Collect ARP and router advertisements to infer the gateways.
+    fn observe_gateway(&mut self, bytes: &[u8]) {
+        if let Some(pdu) = ArpPdu::new(bytes) {
+            if self.arp.macs.is_empty() && self.arp.requests.is_empty() {
+                println!("Found ARP Packet...");
+                pdu.print();
+            }
+            self.arp.add(&pdu);
+        } else if let Some(pdu) = RaPdu::new(bytes) {
+            // Routers that aren't a default gateway also advertise, prefer one that is.
+            let stored = self.ra.as_deref().and_then(RaPdu::new);
+            if stored.is_none() {
+                println!("Found Router Advertisement Packet...");
+            }
+            if pdu.is_default_router() || !stored.is_some_and(|pdu| pdu.is_default_router()) {
+                self.ra = Some(bytes.to_vec());
             }
         }
     }
-}
 
-fn handle_lldp(bytes: &[u8]) {
-    if let Some(pdu) = LldpPdu::new(bytes) {
-        pdu.print();
+    /// Check if nothing specific to the port was seen. ARP and router
+    /// advertisements are seen on every LAN, so they don't count on their own.
+    fn is_empty(&self) -> bool {
+        self.lacp.is_none()
+            && self.udld.is_none()
+            && self.mvrp.is_none()
+            && self.cfm.is_empty()
+            && self.ptp.is_none()
+            && self.vlans.tagged == 0
+            && self.eapol.is_none()
+    }
+
+    fn print(&self) {
+        self.vlans.print();
+        if let Some(pdu) = self.lacp.as_deref().and_then(LacpPdu::new) {
+            pdu.print();
+        }
+        if let Some(pdu) = self.udld.as_deref().and_then(UdldPdu::new) {
+            pdu.print();
+        }
+        for pdu in self.cfm.values().filter_map(|bytes| CfmPdu::new(bytes)) {
+            pdu.print();
+        }
+        if let Some(pdu) = self.ptp.as_deref().and_then(PtpPdu::new) {
+            pdu.print();
+        }
+        if let Some(pdu) = self.eapol.as_deref().and_then(EapolPdu::new) {
+            pdu.print();
+            println!("802.1X active on port, the VLAN may be a guest or auth-fail VLAN.");
+        }
+        self.arp.print();
+        if let Some(pdu) = self.ra.as_deref().and_then(RaPdu::new) {
+            pdu.print();
+        }
+        if let Some(pdu) = self.mvrp.as_deref().and_then(MvrpPdu::new) {
+            pdu.print();
+            println!(
+                "{} Vlans: {}",
+                pdu.protocol_name(),
+                mvrp::vlan_ranges(&self.mvrp_vlans)
+            );
+        }
+    }
+
+    /// Return the entries of everything observed, empty if nothing was seen.
+    fn entries(&self) -> Vec<Entry> {
         let mut entries = Vec::<Entry>::new();
+        let lacp = self.lacp.as_deref().and_then(LacpPdu::new);
+        let (actor, partner, state) = match &lacp {
+            Some(pdu) => (
+                pdu.actor.system.to_string(),
+                pdu.partner.system.to_string(),
+                pdu.aggregation().to_string(),
+            ),
+            None => (String::new(), String::new(), String::new()),
+        };
+        entries.push(Entry::new("LacpActor", &actor));
+        entries.push(Entry::new("LacpPartner", &partner));
+        entries.push(Entry::new("LacpState", &state));
+
+        let udld = self.udld.as_deref().and_then(UdldPdu::new);
+        let (device, port, echo) = match &udld {
+            Some(pdu) => (
+                pdu.switch(),
+                pdu.switch_port.to_string(),
+                pdu.echoes(&self.device_ids).to_string(),
+            ),
+            None => (String::new(), String::new(), String::new()),
+        };
+        entries.push(Entry::new("UdldDevice", &device));
+        entries.push(Entry::new("UdldPort", &port));
+        entries.push(Entry::new("UdldEcho", &echo));
+
         entries.push(Entry::new(
-            "LastWrite",
-            &chrono::offset::Utc::now().to_string(),
+            "MvrpVlans",
+            &mvrp::vlan_ranges(&self.mvrp_vlans),
         ));
-        entries.push(Entry::new("Switch", &pdu.system_description));
-        entries.push(Entry::new("SwitchDisplay", &pdu.system_description));
-        entries.push(Entry::new("Port", &pdu.port_description));
-        entries.push(Entry::new("Vlan", &pdu.vlan));
-
-        match reg::write_hklm(&entries, REGISTRY_PATH) {
-            Ok(_) => {
-                std::process::exit(SUCCESS);
-            }
-            Err(e) => {
-                println!("Failure writing to registry \"{}\"\n{}", REGISTRY_PATH, e);
-                std::process::exit(REGISTRY_WRITE_FAILURE);
-            }
+
+        let cfm: Vec<CfmPdu> = self
+            .cfm
+            .values()
+            .filter_map(|bytes| CfmPdu::new(bytes))
+            .collect();
+        let join = |f: fn(&CfmPdu) -> String| -> String {
+            cfm.iter().map(f).collect::<Vec<String>>().join(", ")
+        };
+        entries.push(Entry::new(
+            "CfmLevel",
+            &join(|pdu| pdu.md_level.to_string()),
+        ));
+        entries.push(Entry::new("CfmMaid", &join(|pdu| pdu.maid())));
+        entries.push(Entry::new("CfmMepId", &join(|pdu| pdu.mep_id.to_string())));
+        entries.push(Entry::new(
+            "CfmInterval",
+            &join(|pdu| pdu.interval().to_string()),
+        ));
+        entries.push(Entry::new("CfmRdi", &join(|pdu| pdu.rdi().to_string())));
+
+        let ptp = self.ptp.as_deref().and_then(PtpPdu::new);
+        let (domain, port) = match &ptp {
+            Some(pdu) => (pdu.domain_number.to_string(), pdu.port_identity()),
+            None => (String::new(), String::new()),
+        };
+        let (grandmaster, class, accuracy, priority) =
+            match ptp.as_ref().and_then(|pdu| pdu.announce.as_ref()) {
+                Some(announce) => (
+                    announce.grandmaster_identity.to_string(),
+                    announce.clock_class.to_string(),
+                    announce.clock_accuracy_name().to_string(),
+                    format!("{}/{}", announce.priority1, announce.priority2),
+                ),
+                None => (String::new(), String::new(), String::new(), String::new()),
+            };
+        entries.push(Entry::new("PtpDomain", &domain));
+        entries.push(Entry::new("PtpPort", &port));
+        entries.push(Entry::new("PtpGrandmaster", &grandmaster));
+        entries.push(Entry::new("PtpClockClass", &class));
+        entries.push(Entry::new("PtpClockAccuracy", &accuracy));
+        entries.push(Entry::new("PtpPriority", &priority));
+
+        let eapol = self.eapol.as_deref().and_then(EapolPdu::new);
+        entries.push(Entry::new("Dot1x", &eapol.is_some().to_string()));
+        entries.push(Entry::new(
+            "Dot1xMessage",
+            &eapol.map(|pdu| pdu.message()).unwrap_or_default(),
+        ));
+
+        let gateway = self.arp.gateway().map(|ip| ip.to_string());
+        entries.push(Entry::new("GatewayIp", &gateway.unwrap_or_default()));
+        entries.push(Entry::new("GatewayMac", &self.arp.gateway_mac()));
+
+        let ra = self.ra.as_deref().and_then(RaPdu::new);
+        let (prefixes, flags, lifetime) = match &ra {
+            Some(pdu) => (
+                pdu.prefixes(),
+                pdu.flag_names(),
+                pdu.router_lifetime.to_string(),
+            ),
+            None => (String::new(), String::new(), String::new()),
+        };
+        // Only a router with a lifetime is a default gateway.
+        let (router_ip, router_mac) = match ra.as_ref().filter(|pdu| pdu.is_default_router()) {
+            Some(pdu) => (pdu.router_ip.to_string(), pdu.router_mac.to_string()),
+            None => (String::new(), String::new()),
+        };
+        entries.push(Entry::new("Ipv6GatewayIp", &router_ip));
+        entries.push(Entry::new("Ipv6GatewayMac", &router_mac));
+        entries.push(Entry::new("Ipv6Prefixes", &prefixes));
+        entries.push(Entry::new("RaFlags", &flags));
+        entries.push(Entry::new("RouterLifetime", &lifetime));
+
+        entries.push(Entry::new("Trunk", &self.vlans.is_trunk().to_string()));
+        entries.push(Entry::new("TaggedVlans", &self.vlans.vlans()));
+        entries.push(Entry::new("TaggedPcp", &self.vlans.pcps()));
+        entries
+    }
+}
+
+/// Write the entries and anything observed to the registry, remove the
+/// stale values and exit.
+fn write_entries(mut entries: Vec<Entry>, stale: &[&str], observed: &Observed) {
+    observed.print();
+    entries.extend(observed.entries());
+    // Stale values are only removed once everything else is written.
+    match reg::write_hklm(&entries, REGISTRY_PATH)
+        .and_then(|_| reg::delete_hklm(stale, REGISTRY_PATH))
+    {
+        Ok(_) => {
+            std::process::exit(SUCCESS);
+        }
+        Err(e) => {
+            println!("Failure writing to registry \"{}\"\n{}", REGISTRY_PATH, e);
+            std::process::exit(REGISTRY_WRITE_FAILURE);
         }
     }
 }
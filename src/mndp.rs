@@ -0,0 +1,214 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use pnet::packet::{
+    ethernet::{EtherTypes, EthernetPacket},
+    ip::IpNextHeaderProtocols,
+    ipv4::Ipv4Packet,
+    ipv6::Ipv6Packet,
+    udp::UdpPacket,
+    Packet,
+};
+
+use crate::util::{hex_string, remove_chars};
+
+#[allow(non_snake_case)]
+pub mod TlvType {
+    pub const MAC_ADDRESS: u16 = 0x0001;
+    pub const IDENTITY: u16 = 0x0005;
+    pub const VERSION: u16 = 0x0007;
+    pub const PLATFORM: u16 = 0x0008;
+    pub const UPTIME: u16 = 0x000A;
+    pub const BOARD: u16 = 0x000C;
+    pub const IPV6_ADDRESS: u16 = 0x000F;
+    pub const INTERFACE_NAME: u16 = 0x0010;
+    pub const IPV4_ADDRESS: u16 = 0x0011;
+}
+
+/// MNDP is sent over UDP to this port instead of a multicast MAC.
+pub const UDP_PORT: u16 = 5678;
+
+/// Represents a MikroTik Neighbor Discovery Protocol datagram received over
+/// Ethernet/IPv4 or Ethernet/IPv6.
+pub struct MndpPdu<'a> {
+    bytes: &'a [u8],
+    valid: bool,
+    pub switch_name: String,
+    pub switch_mac: String,
+    pub switch_ip: String,
+    pub switch_ipv6: String,
+    pub switch_port: String,
+    pub switch_interface: String,
+    pub switch_version: String,
+    pub switch_platform: String,
+    pub switch_board: String,
+    pub uptime: Option<u32>, // Seconds
+}
+
+impl<'a> MndpPdu<'a> {
+    // Attempt to create a new MNDP PDU with the given packet.
+    pub fn new(bytes: &'a [u8]) -> Option<Self> {
+        let (source, payload) = Self::udp_payload(bytes)?;
+
+        let mut pdu = Self {
+            bytes,
+            valid: false,
+            switch_name: String::new(),
+            switch_mac: String::new(),
+            switch_ip: String::new(),
+            switch_ipv6: String::new(),
+            switch_port: String::new(),
+            switch_interface: String::new(),
+            switch_version: String::new(),
+            switch_platform: String::new(),
+            switch_board: String::new(),
+            uptime: None,
+        };
+
+        // The sender's address is used unless an address TLV is present.
+        match source {
+            IpAddr::V4(ip) => pdu.switch_ip = ip.to_string(),
+            IpAddr::V6(ip) => pdu.switch_ipv6 = ip.to_string(),
+        }
+
+        // Header (2), sequence number (2)
+        let mut index = 4;
+        while index + 4 <= payload.len() {
+            // Type/Length, the length only covers the value.
+            let t = u16::from_be_bytes([payload[index], payload[index + 1]]);
+            let l = u16::from_be_bytes([payload[index + 2], payload[index + 3]]) as usize;
+            if (index + 4 + l) > payload.len() {
+                break;
+            }
+            let value = &payload[index + 4..index + 4 + l];
+
+            match t {
+                TlvType::MAC_ADDRESS => {
+                    pdu.switch_mac = hex_string(value);
+                }
+                TlvType::IDENTITY => {
+                    pdu.switch_name = String::from_utf8_lossy(value).to_string();
+                    pdu.valid = true;
+                }
+                TlvType::VERSION => {
+                    pdu.switch_version = String::from_utf8_lossy(value).to_string();
+                }
+                TlvType::PLATFORM => {
+                    pdu.switch_platform = String::from_utf8_lossy(value).to_string();
+                }
+                TlvType::UPTIME if l == 4 => {
+                    // Uptime is the only little endian value.
+                    pdu.uptime = Some(u32::from_le_bytes([value[0], value[1], value[2], value[3]]));
+                }
+                TlvType::BOARD => {
+                    pdu.switch_board = String::from_utf8_lossy(value).to_string();
+                }
+                TlvType::IPV6_ADDRESS => {
+                    if let Ok(octets) = <[u8; 16]>::try_from(value) {
+                        pdu.switch_ipv6 = Ipv6Addr::from(octets).to_string();
+                    }
+                }
+                TlvType::INTERFACE_NAME => {
+                    pdu.switch_interface = String::from_utf8_lossy(value).to_string();
+                    pdu.switch_port = remove_chars(&pdu.switch_interface);
+                }
+                TlvType::IPV4_ADDRESS => {
+                    if let Ok(octets) = <[u8; 4]>::try_from(value) {
+                        pdu.switch_ip = Ipv4Addr::from(octets).to_string();
+                    }
+                }
+                _ => {}
+            }
+
+            // Go to the next TLV
+            index += 4 + l;
+        }
+
+        if pdu.valid {
+            Some(pdu)
+        } else {
+            None
+        }
+    }
+
+    /// Check if the packet is a UDP datagram sent to the MNDP port.
+    pub fn is_mndp(bytes: &[u8]) -> bool {
+        Self::udp_payload(bytes).is_some()
+    }
+
+    /// Return the source IP and UDP payload of an MNDP datagram. The payload
+    /// is borrowed from the packet since every IP frame is checked.
+    fn udp_payload(bytes: &[u8]) -> Option<(IpAddr, &[u8])> {
+        let ethernet = EthernetPacket::new(bytes)?;
+        let (source, offset) = match ethernet.get_ethertype() {
+            EtherTypes::Ipv4 => {
+                let ipv4 = Ipv4Packet::new(ethernet.payload())?;
+                if ipv4.get_next_level_protocol() != IpNextHeaderProtocols::Udp {
+                    return None;
+                }
+                let header = ipv4.get_header_length() as usize * 4;
+                (IpAddr::V4(ipv4.get_source()), 14 + header)
+            }
+            EtherTypes::Ipv6 => {
+                let ipv6 = Ipv6Packet::new(ethernet.payload())?;
+                if ipv6.get_next_header() != IpNextHeaderProtocols::Udp {
+                    return None;
+                }
+                (IpAddr::V6(ipv6.get_source()), 14 + 40)
+            }
+            _ => return None,
+        };
+
+        // Source port (2), destination port (2), length (2), checksum (2)
+        let udp = UdpPacket::new(bytes.get(offset..)?)?;
+        if udp.get_destination() != UDP_PORT {
+            return None;
+        }
+        let end = (offset + udp.get_length() as usize).min(bytes.len());
+        Some((source, bytes.get(offset + 8..end)?))
+    }
+
+    pub fn switch(&self) -> String {
+        let mut switch = String::new();
+        if !self.switch_name.is_empty() {
+            switch.push_str(&self.switch_name);
+        }
+        if !self.switch_ip.is_empty() {
+            if !switch.is_empty() {
+                switch.push(' ');
+            }
+            switch.push_str(&format!("({})", &self.switch_ip));
+        }
+        switch
+    }
+
+    pub fn switch_display(&self) -> String {
+        self.switch_name.to_string()
+    }
+
+    /// Return the platform and board name, e.g. "MikroTik RB4011iGS+".
+    pub fn platform(&self) -> String {
+        let mut platform = self.switch_platform.to_string();
+        if !self.switch_board.is_empty() {
+            if !platform.is_empty() {
+                platform.push(' ');
+            }
+            platform.push_str(&self.switch_board);
+        }
+        platform
+    }
+
+    pub fn print(&self) {
+        println!();
+        println!("Switch:   {} ({})", self.switch_name, self.switch_ip);
+        println!("IPv6:     {}", self.switch_ipv6);
+        println!("MAC:      {}", self.switch_mac);
+        println!("Platform: {}", self.platform());
+        println!("Version:  {}", self.switch_version);
+        println!("Port:     {}", self.switch_interface);
+        if let Some(uptime) = self.uptime {
+            println!("Uptime:   {}s", uptime);
+        }
+        println!("Bytes:    {:02X?}", self.bytes);
+        println!();
+    }
+}
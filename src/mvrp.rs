@@ -0,0 +1,321 @@
+use std::collections::BTreeSet;
+
+/// MVRP ethertype. GVRP uses an 802.3 length and LLC header instead.
+pub const ETHERTYPE: u16 = 0x88F5;
+
+/// LLC header that precedes every GARP PDU (DSAP, SSAP, control).
+const LLC_HEADER: [u8; 3] = [0x42, 0x42, 0x03];
+
+/// Attribute type of a VLAN identifier in both MVRP and GVRP.
+const VLAN_ATTRIBUTE: u8 = 0x01;
+
+/// MRP attribute events packed three to a byte.
+#[allow(non_snake_case)]
+pub mod MrpEvent {
+    pub const NEW: u8 = 0;
+    pub const JOIN_IN: u8 = 1;
+    pub const IN: u8 = 2;
+    pub const JOIN_MT: u8 = 3;
+    pub const MT: u8 = 4;
+    pub const LV: u8 = 5;
+}
+
+/// GARP attribute events, one per attribute.
+#[allow(non_snake_case)]
+pub mod GarpEvent {
+    pub const LEAVE_ALL: u8 = 0;
+    pub const JOIN_EMPTY: u8 = 1;
+    pub const JOIN_IN: u8 = 2;
+    pub const LEAVE_EMPTY: u8 = 3;
+    pub const LEAVE_IN: u8 = 4;
+    pub const EMPTY: u8 = 5;
+}
+
+/// An event for a single VLAN attribute.
+pub struct VlanEvent {
+    pub vlan_id: u16,
+    pub event: u8,
+}
+
+/// Represents an MVRP or legacy GVRP PDU received on 01:80:C2:00:00:21.
+pub struct MvrpPdu<'a> {
+    bytes: &'a [u8],
+    pub gvrp: bool,
+    pub leave_all: bool,
+    pub events: Vec<VlanEvent>,
+}
+
+impl<'a> MvrpPdu<'a> {
+    // Attempt to create a new MVRP or GVRP PDU with the given packet.
+    pub fn new(bytes: &'a [u8]) -> Option<Self> {
+        if bytes.len() < 15 {
+            return None;
+        }
+
+        let mut pdu = Self {
+            bytes,
+            gvrp: false,
+            leave_all: false,
+            events: Vec::new(),
+        };
+
+        // MVRP: Ethernet header (14), protocol version (1)
+        // GVRP: 802.3 header (14), LLC (3), protocol ID (2)
+        if u16::from_be_bytes([bytes[12], bytes[13]]) == ETHERTYPE {
+            pdu.parse_mrp(&bytes[15..]);
+        } else if bytes.len() >= 19 && bytes[14..17] == LLC_HEADER && bytes[17..19] == [0, 1] {
+            pdu.gvrp = true;
+            pdu.parse_garp(&bytes[19..]);
+        } else {
+            return None;
+        }
+
+        if pdu.events.is_empty() && !pdu.leave_all {
+            None
+        } else {
+            Some(pdu)
+        }
+    }
+
+    /// Messages of attribute type (1), attribute length (1), then vector attributes of
+    /// a vector header (2), first value (n) and three packed events (1) per three values.
+    /// Both lists end with a 0x0000 end mark.
+    fn parse_mrp(&mut self, bytes: &[u8]) {
+        let mut index = 0;
+        while index + 2 <= bytes.len() && bytes[index..index + 2] != [0, 0] {
+            let attribute_type = bytes[index];
+            let attribute_length = bytes[index + 1] as usize;
+            index += 2;
+
+            while index + 2 <= bytes.len() && bytes[index..index + 2] != [0, 0] {
+                let header = u16::from_be_bytes([bytes[index], bytes[index + 1]]);
+                let values = (header & 0x1FFF) as usize;
+                let packed = values.div_ceil(3);
+                if index + 2 + attribute_length + packed > bytes.len() {
+                    return;
+                }
+                if header >> 13 != 0 {
+                    self.leave_all = true;
+                }
+
+                let first = &bytes[index + 2..index + 2 + attribute_length];
+                let events =
+                    &bytes[index + 2 + attribute_length..index + 2 + attribute_length + packed];
+                if attribute_type == VLAN_ATTRIBUTE && attribute_length == 2 {
+                    let first = u16::from_be_bytes([first[0], first[1]]);
+                    for i in 0..values {
+                        // Each byte is ((event1 * 6) + event2) * 6 + event3
+                        let byte = events[i / 3];
+                        let event = match i % 3 {
+                            0 => byte / 36,
+                            1 => (byte / 6) % 6,
+                            _ => byte % 6,
+                        };
+                        self.events.push(VlanEvent {
+                            vlan_id: first.wrapping_add(i as u16) & 0x0FFF,
+                            event,
+                        });
+                    }
+                }
+                index += 2 + attribute_length + packed;
+            }
+
+            // Skip the vector attribute end mark
+            index += 2;
+        }
+    }
+
+    /// Messages of attribute type (1), then attributes of length (1), event (1) and
+    /// value (n) where the length covers all three. Both lists end with a 0x00 end mark.
+    fn parse_garp(&mut self, bytes: &[u8]) {
+        let mut index = 0;
+        while index < bytes.len() && bytes[index] != 0 {
+            let attribute_type = bytes[index];
+            index += 1;
+
+            while index + 2 <= bytes.len() && bytes[index] != 0 {
+                let l = bytes[index] as usize;
+                if l < 2 || index + l > bytes.len() {
+                    return;
+                }
+                let event = bytes[index + 1];
+                if event == GarpEvent::LEAVE_ALL {
+                    self.leave_all = true;
+                } else if attribute_type == VLAN_ATTRIBUTE && l == 4 {
+                    self.events.push(VlanEvent {
+                        vlan_id: u16::from_be_bytes([bytes[index + 2], bytes[index + 3]]) & 0x0FFF,
+                        event,
+                    });
+                }
+                index += l;
+            }
+
+            // Skip the attribute end mark
+            index += 1;
+        }
+    }
+
+    /// Check if the event declares the VLAN, rather than only registering or
+    /// withdrawing it.
+    pub fn is_declaration(&self, event: u8) -> bool {
+        if self.gvrp {
+            event == GarpEvent::JOIN_EMPTY || event == GarpEvent::JOIN_IN
+        } else {
+            matches!(event, MrpEvent::NEW | MrpEvent::JOIN_IN | MrpEvent::JOIN_MT)
+        }
+    }
+
+    /// Check if the event withdraws the VLAN.
+    pub fn is_withdrawal(&self, event: u8) -> bool {
+        if self.gvrp {
+            event == GarpEvent::LEAVE_EMPTY || event == GarpEvent::LEAVE_IN
+        } else {
+            event == MrpEvent::LV
+        }
+    }
+
+    pub fn event_name(&self, event: u8) -> &'static str {
+        match (self.gvrp, event) {
+            (false, MrpEvent::NEW) => "New",
+            (false, MrpEvent::JOIN_IN) => "JoinIn",
+            (false, MrpEvent::IN) => "In",
+            (false, MrpEvent::JOIN_MT) => "JoinMt",
+            (false, MrpEvent::MT) => "Mt",
+            (false, MrpEvent::LV) => "Lv",
+            (true, GarpEvent::JOIN_EMPTY) => "JoinEmpty",
+            (true, GarpEvent::JOIN_IN) => "JoinIn",
+            (true, GarpEvent::LEAVE_EMPTY) => "LeaveEmpty",
+            (true, GarpEvent::LEAVE_IN) => "LeaveIn",
+            (true, GarpEvent::EMPTY) => "Empty",
+            _ => "Unknown",
+        }
+    }
+
+    /// Add the declared VLANs to the set and remove the withdrawn ones.
+    pub fn update(&self, vlans: &mut BTreeSet<u16>) {
+        for event in &self.events {
+            if self.is_declaration(event.event) {
+                vlans.insert(event.vlan_id);
+            } else if self.is_withdrawal(event.event) {
+                vlans.remove(&event.vlan_id);
+            }
+        }
+    }
+
+    /// Return the VLANs declared in this PDU.
+    pub fn declared(&self) -> BTreeSet<u16> {
+        let mut vlans = BTreeSet::new();
+        self.update(&mut vlans);
+        vlans
+    }
+
+    pub fn protocol_name(&self) -> &'static str {
+        if self.gvrp {
+            "GVRP"
+        } else {
+            "MVRP"
+        }
+    }
+
+    pub fn print(&self) {
+        println!();
+        println!("Protocol: {}", self.protocol_name());
+        println!("Declared: {}", vlan_ranges(&self.declared()));
+        println!(
+            "Events:   {}",
+            self.events
+                .iter()
+                .map(|event| format!("{} {}", event.vlan_id, self.event_name(event.event)))
+                .collect::<Vec<String>>()
+                .join(", ")
+        );
+        if self.leave_all {
+            println!("LeaveAll: true");
+        }
+        println!("Bytes:    {:02X?}", self.bytes);
+        println!();
+    }
+}
+
+/// Return the VLANs as comma separated ranges, e.g. "1, 10-20, 30".
+pub fn vlan_ranges(vlans: &BTreeSet<u16>) -> String {
+    let mut ranges: Vec<(u16, u16)> = Vec::new();
+    for &vlan in vlans {
+        match ranges.last_mut() {
+            Some((_, end)) if *end + 1 == vlan => *end = vlan,
+            _ => ranges.push((vlan, vlan)),
+        }
+    }
+    ranges
+        .iter()
+        .map(|(start, end)| {
+            if start == end {
+                start.to_string()
+            } else {
+                format!("{}-{}", start, end)
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An MVRP frame with a single VLAN vector attribute.
+    fn mrp_frame(header: u16, first_vlan: u16, events: &[u8]) -> Vec<u8> {
+        let mut bytes = vec![0x01, 0x80, 0xC2, 0x00, 0x00, 0x21];
+        bytes.extend_from_slice(&[0x00, 0x11, 0x22, 0x33, 0x44, 0x55]);
+        bytes.extend_from_slice(&ETHERTYPE.to_be_bytes());
+        bytes.extend_from_slice(&[0x00, VLAN_ATTRIBUTE, 2]);
+        bytes.extend_from_slice(&header.to_be_bytes());
+        bytes.extend_from_slice(&first_vlan.to_be_bytes());
+        bytes.extend_from_slice(events);
+        bytes.extend_from_slice(&[0, 0, 0, 0]);
+        bytes
+    }
+
+    #[test]
+    fn unpacks_three_events_per_byte() {
+        // JoinIn, New, Lv then JoinMt, In and a padding event
+        let first = (MrpEvent::JOIN_IN * 6 + MrpEvent::NEW) * 6 + MrpEvent::LV;
+        let second = (MrpEvent::JOIN_MT * 6 + MrpEvent::IN) * 6;
+        let bytes = mrp_frame(5, 100, &[first, second]);
+        let pdu = MvrpPdu::new(&bytes).unwrap();
+
+        let events: Vec<(u16, u8)> = pdu
+            .events
+            .iter()
+            .map(|event| (event.vlan_id, event.event))
+            .collect();
+        assert_eq!(
+            events,
+            [
+                (100, MrpEvent::JOIN_IN),
+                (101, MrpEvent::NEW),
+                (102, MrpEvent::LV),
+                (103, MrpEvent::JOIN_MT),
+                (104, MrpEvent::IN),
+            ]
+        );
+        assert!(!pdu.leave_all);
+        assert_eq!(vlan_ranges(&pdu.declared()), "100-101, 103");
+    }
+
+    #[test]
+    fn reads_leave_all_from_the_vector_header() {
+        let bytes = mrp_frame(0x2000 | 1, 10, &[MrpEvent::MT * 36]);
+        let pdu = MvrpPdu::new(&bytes).unwrap();
+        assert!(pdu.leave_all);
+        assert_eq!(pdu.events.len(), 1);
+        assert_eq!(pdu.events[0].event, MrpEvent::MT);
+    }
+
+    #[test]
+    fn rejects_truncated_events() {
+        // Four values need two packed bytes
+        let bytes = mrp_frame(4, 10, &[]);
+        assert!(MvrpPdu::new(&bytes[..bytes.len() - 4]).is_none());
+    }
+}
@@ -4,7 +4,10 @@ use std::{
     time::{Duration, Instant},
 };
 
-use crate::exit_codes::{UNABLE_TO_CREATE_CHANNEL, UNHANDLED_CHANNEL_TYPE};
+use crate::{
+    exit_codes::{UNABLE_TO_CREATE_CHANNEL, UNHANDLED_CHANNEL_TYPE},
+    vlan::{self, VlanTag},
+};
 
 /// Represents a NIC and all of it's available interfaces. It will filter out non-virtual,
 /// wired interfaces. You can then listen on those interfaces and apply a closure to each
@@ -30,10 +33,12 @@ impl Nic {
     }
 
     /// Listen on the filtered interfaces. Do something with each packet
-    /// via a closure.
-    pub fn listen_wired<F>(&self, duration: Duration, handle_packet: F)
+    /// via a closure. Packets are untagged first and their VLAN tags are
+    /// passed along with them. The closure returns false to stop listening
+    /// before the duration is over.
+    pub fn listen_wired<F>(&self, duration: Duration, mut handle_packet: F)
     where
-        F: Fn(&[u8]),
+        F: FnMut(&[u8], &[VlanTag]) -> bool,
     {
         if self.interfaces_wired.len() == 0 {
             println!("No wired interfaces to listen on.");
@@ -72,13 +77,31 @@ impl Nic {
 
                 // Handle packets.
                 match rx.next() {
-                    Ok(packet_bytes) => handle_packet(packet_bytes),
+                    Ok(packet_bytes) => {
+                        let (bytes, tags) = vlan::untag(packet_bytes);
+                        if !handle_packet(&bytes, &tags) {
+                            println!(
+                                "\nFinished listening on \"{}\"\n",
+                                Self::interface_string(interface)
+                            );
+                            return;
+                        }
+                    }
                     Err(e) => println!("Error - Unable to receive packet: {}", e),
                 }
             }
         }
     }
 
+    /// Return the MAC addresses of the filtered interfaces.
+    pub fn wired_macs(&self) -> Vec<String> {
+        self.interfaces_wired
+            .iter()
+            .filter_map(|iface| iface.mac)
+            .map(|mac| mac.to_string())
+            .collect()
+    }
+
     /// Processes all found interfaces puts them in their respective lists.
     /// For now, this just filters out non-virtual, wired interfaces.
     fn filter_interfaces(&mut self) {
@@ -0,0 +1,167 @@
+use crate::util::hex_string;
+
+/// PTP ethertype, sent to 01:1B:19:00:00:00 or 01:80:C2:00:00:0E for peer delay.
+pub const ETHERTYPE: u16 = 0x88F7;
+
+#[allow(non_snake_case)]
+pub mod MessageType {
+    pub const SYNC: u8 = 0x00;
+    pub const ANNOUNCE: u8 = 0x0B;
+}
+
+/// Grandmaster information carried by an Announce message.
+#[allow(dead_code)]
+pub struct Announce {
+    pub current_utc_offset: i16,
+    pub priority1: u8,
+    pub clock_class: u8,
+    pub clock_accuracy: u8,
+    pub offset_scaled_log_variance: u16,
+    pub priority2: u8,
+    pub grandmaster_identity: String,
+    pub steps_removed: u16,
+    pub time_source: u8,
+}
+
+impl Announce {
+    /// Origin timestamp (10), current UTC offset (2), reserved (1), priority 1 (1),
+    /// clock class (1), clock accuracy (1), offset scaled log variance (2),
+    /// priority 2 (1), grandmaster identity (8), steps removed (2), time source (1)
+    fn parse(bytes: &[u8]) -> Self {
+        Self {
+            current_utc_offset: i16::from_be_bytes([bytes[10], bytes[11]]),
+            priority1: bytes[13],
+            clock_class: bytes[14],
+            clock_accuracy: bytes[15],
+            offset_scaled_log_variance: u16::from_be_bytes([bytes[16], bytes[17]]),
+            priority2: bytes[18],
+            grandmaster_identity: hex_string(&bytes[19..27]),
+            steps_removed: u16::from_be_bytes([bytes[27], bytes[28]]),
+            time_source: bytes[29],
+        }
+    }
+
+    pub fn clock_accuracy_name(&self) -> &'static str {
+        match self.clock_accuracy {
+            0x20 => "25ns",
+            0x21 => "100ns",
+            0x22 => "250ns",
+            0x23 => "1us",
+            0x24 => "2.5us",
+            0x25 => "10us",
+            0x26 => "25us",
+            0x27 => "100us",
+            0x28 => "250us",
+            0x29 => "1ms",
+            0x2A => "2.5ms",
+            0x2B => "10ms",
+            0x2C => "25ms",
+            0x2D => "100ms",
+            0x2E => "250ms",
+            0x2F => "1s",
+            0x30 => "10s",
+            0x31 => ">10s",
+            _ => "Unknown",
+        }
+    }
+
+    pub fn time_source_name(&self) -> &'static str {
+        match self.time_source {
+            0x10 => "Atomic Clock",
+            0x20 => "GPS",
+            0x30 => "Terrestrial Radio",
+            0x40 => "PTP",
+            0x50 => "NTP",
+            0x60 => "Hand Set",
+            0x90 => "Other",
+            0xA0 => "Internal Oscillator",
+            _ => "Unknown",
+        }
+    }
+}
+
+/// Represents an IEEE 1588v2 Sync or Announce message received over Ethernet.
+#[allow(dead_code)]
+pub struct PtpPdu<'a> {
+    bytes: &'a [u8],
+    pub message_type: u8,
+    pub version: u8,
+    pub domain_number: u8,
+    pub flags: u16,
+    pub clock_identity: String, // Of the sending port
+    pub port_number: u16,
+    pub sequence_id: u16,
+    pub announce: Option<Announce>,
+}
+
+impl<'a> PtpPdu<'a> {
+    // Attempt to create a new PTP PDU with the given packet.
+    pub fn new(bytes: &'a [u8]) -> Option<Self> {
+        // Ethernet header (14), PTP header (34), origin timestamp (10)
+        if bytes.len() < 58 || u16::from_be_bytes([bytes[12], bytes[13]]) != ETHERTYPE {
+            return None;
+        }
+
+        // Transport specific (4 bits), message type (4 bits), version (1), length (2),
+        // domain (1), reserved (1), flags (2), correction (8), reserved (4),
+        // source port identity (10), sequence ID (2), control (1), log interval (1)
+        let ptp = &bytes[14..];
+        let message_type = ptp[0] & 0x0F;
+        let version = ptp[1] & 0x0F;
+        if version != 2 {
+            return None;
+        }
+        let announce = match message_type {
+            MessageType::SYNC => None,
+            MessageType::ANNOUNCE if ptp.len() >= 64 => Some(Announce::parse(&ptp[34..])),
+            _ => return None,
+        };
+
+        Some(Self {
+            bytes,
+            message_type,
+            version,
+            domain_number: ptp[4],
+            flags: u16::from_be_bytes([ptp[6], ptp[7]]),
+            clock_identity: hex_string(&ptp[20..28]),
+            port_number: u16::from_be_bytes([ptp[28], ptp[29]]),
+            sequence_id: u16::from_be_bytes([ptp[30], ptp[31]]),
+            announce,
+        })
+    }
+
+    pub fn is_announce(&self) -> bool {
+        self.announce.is_some()
+    }
+
+    /// Return the port identity of the sender, e.g. "00:11:22:FF:FE:33:44:55/1".
+    pub fn port_identity(&self) -> String {
+        format!("{}/{}", self.clock_identity, self.port_number)
+    }
+
+    pub fn print(&self) {
+        println!();
+        match &self.announce {
+            Some(_) => println!("PTP:         v{} Announce", self.version),
+            None => println!("PTP:         v{} Sync", self.version),
+        }
+        println!("Domain:      {}", self.domain_number);
+        println!("Port:        {}", self.port_identity());
+        if let Some(announce) = &self.announce {
+            println!("Grandmaster: {}", announce.grandmaster_identity);
+            println!("Priority:    {}/{}", announce.priority1, announce.priority2);
+            println!(
+                "Class:       {} ({})",
+                announce.clock_class,
+                announce.clock_accuracy_name()
+            );
+            println!(
+                "Source:      {} ({} steps)",
+                announce.time_source_name(),
+                announce.steps_removed
+            );
+        }
+        println!("Bytes:       {:02X?}", self.bytes);
+        println!();
+    }
+}
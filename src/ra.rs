@@ -0,0 +1,179 @@
+use std::net::Ipv6Addr;
+
+use pnet::packet::{
+    ethernet::{EtherTypes, EthernetPacket},
+    icmpv6::{Icmpv6Packet, Icmpv6Types},
+    ip::IpNextHeaderProtocols,
+    ipv6::Ipv6Packet,
+    Packet,
+};
+
+use crate::util::hex_string;
+
+#[allow(non_snake_case)]
+pub mod OptionType {
+    pub const SOURCE_LINK_LAYER_ADDRESS: u8 = 1;
+    pub const PREFIX_INFORMATION: u8 = 3;
+    pub const MTU: u8 = 5;
+}
+
+#[allow(non_snake_case)]
+pub mod Flag {
+    pub const MANAGED: u8 = 0x80;
+    pub const OTHER: u8 = 0x40;
+}
+
+/// An on-link prefix advertised by the router.
+#[allow(dead_code)]
+pub struct Prefix {
+    pub prefix: Ipv6Addr,
+    pub length: u8,
+    pub on_link: bool,
+    pub autonomous: bool, // SLAAC
+    pub valid_lifetime: u32,
+    pub preferred_lifetime: u32,
+}
+
+/// Represents an ICMPv6 Router Advertisement received over Ethernet/IPv6.
+#[allow(dead_code)]
+pub struct RaPdu<'a> {
+    bytes: &'a [u8],
+    pub router_ip: Ipv6Addr,
+    pub router_mac: String,
+    pub hop_limit: u8,
+    pub flags: u8,
+    pub router_lifetime: u16, // Seconds, 0 if the router is not a default gateway
+    pub prefixes: Vec<Prefix>,
+    pub mtu: Option<u32>,
+}
+
+impl<'a> RaPdu<'a> {
+    // Attempt to create a new Router Advertisement with the given packet.
+    pub fn new(bytes: &'a [u8]) -> Option<Self> {
+        let ethernet = EthernetPacket::new(bytes)?;
+        if ethernet.get_ethertype() != EtherTypes::Ipv6 {
+            return None;
+        }
+        let ipv6 = Ipv6Packet::new(ethernet.payload())?;
+        if ipv6.get_next_header() != IpNextHeaderProtocols::Icmpv6 {
+            return None;
+        }
+        let icmpv6 = Icmpv6Packet::new(ipv6.payload())?;
+        if icmpv6.get_icmpv6_type() != Icmpv6Types::RouterAdvert {
+            return None;
+        }
+
+        // Type (1), code (1), checksum (2), hop limit (1), flags (1),
+        // router lifetime (2), reachable time (4), retransmit timer (4)
+        let ra = icmpv6.packet();
+        if ra.len() < 16 {
+            return None;
+        }
+        let mut pdu = Self {
+            bytes,
+            router_ip: ipv6.get_source(),
+            // The source link-layer address option is preferred if present.
+            router_mac: ethernet.get_source().to_string().to_uppercase(),
+            hop_limit: ra[4],
+            flags: ra[5],
+            router_lifetime: u16::from_be_bytes([ra[6], ra[7]]),
+            prefixes: Vec::new(),
+            mtu: None,
+        };
+
+        // Type (1), length (1) in units of 8 bytes including the header
+        let mut index = 16;
+        while index + 2 <= ra.len() {
+            let t = ra[index];
+            let l = ra[index + 1] as usize * 8;
+            if l == 0 || (index + l) > ra.len() {
+                break;
+            }
+            let value = &ra[index + 2..index + l];
+
+            match t {
+                OptionType::SOURCE_LINK_LAYER_ADDRESS if value.len() >= 6 => {
+                    pdu.router_mac = hex_string(&value[0..6]);
+                }
+                OptionType::PREFIX_INFORMATION if value.len() >= 30 => {
+                    // Prefix length (1), flags (1), valid lifetime (4),
+                    // preferred lifetime (4), reserved (4), prefix (16)
+                    let mut octets = [0u8; 16];
+                    octets.copy_from_slice(&value[14..30]);
+                    pdu.prefixes.push(Prefix {
+                        prefix: Ipv6Addr::from(octets),
+                        length: value[0],
+                        on_link: value[1] & 0x80 != 0,
+                        autonomous: value[1] & 0x40 != 0,
+                        valid_lifetime: u32::from_be_bytes([
+                            value[2], value[3], value[4], value[5],
+                        ]),
+                        preferred_lifetime: u32::from_be_bytes([
+                            value[6], value[7], value[8], value[9],
+                        ]),
+                    });
+                }
+                OptionType::MTU if value.len() >= 6 => {
+                    pdu.mtu = Some(u32::from_be_bytes([value[2], value[3], value[4], value[5]]));
+                }
+                _ => {}
+            }
+
+            // Go to the next option
+            index += l;
+        }
+
+        Some(pdu)
+    }
+
+    /// Return the prefixes in a String separated by a comma if there is
+    /// more than one, e.g. "2001:db8:1::/64".
+    pub fn prefixes(&self) -> String {
+        self.prefixes
+            .iter()
+            .map(|prefix| format!("{}/{}", prefix.prefix, prefix.length))
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+
+    /// A router lifetime of 0 means the router is not a default gateway.
+    pub fn is_default_router(&self) -> bool {
+        self.router_lifetime != 0
+    }
+
+    /// Return the names of the flags that are set.
+    pub fn flag_names(&self) -> String {
+        let mut flags = Vec::new();
+        if self.flags & Flag::MANAGED != 0 {
+            flags.push("Managed");
+        }
+        if self.flags & Flag::OTHER != 0 {
+            flags.push("Other");
+        }
+        for prefix in &self.prefixes {
+            if prefix.autonomous {
+                flags.push("SLAAC");
+                break;
+            }
+        }
+        flags.join(", ")
+    }
+
+    pub fn print(&self) {
+        println!();
+        println!("Router:   {} ({})", self.router_ip, self.router_mac);
+        println!("Lifetime: {}s", self.router_lifetime);
+        println!("Flags:    {}", self.flag_names());
+        for prefix in &self.prefixes {
+            println!(
+                "Prefix:   {}/{} (Valid {}s, Preferred {}s)",
+                prefix.prefix, prefix.length, prefix.valid_lifetime, prefix.preferred_lifetime
+            );
+        }
+        if let Some(mtu) = self.mtu {
+            println!("MTU:      {}", mtu);
+        }
+        println!("Bytes:    {:02X?}", self.bytes);
+        println!();
+    }
+}
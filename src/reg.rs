@@ -1,4 +1,9 @@
-use winreg::{enums::HKEY_LOCAL_MACHINE, RegKey};
+use std::io::ErrorKind;
+
+use winreg::{
+    enums::{HKEY_LOCAL_MACHINE, KEY_SET_VALUE},
+    RegKey,
+};
 
 use crate::exit_codes::REGISTRY_CREATE_OPEN_FAILURE;
 
@@ -59,3 +64,20 @@ pub fn write_hklm(entries: &[Entry], path: &'static str) -> std::io::Result<()>
         }
     }
 }
+
+/// Delete the given registry values, ignoring any that don't exist.
+pub fn delete_hklm(keys: &[&str], path: &'static str) -> std::io::Result<()> {
+    let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
+    let key = hklm.open_subkey_with_flags(path, KEY_SET_VALUE)?;
+    for name in keys {
+        match key.delete_value(name) {
+            Ok(_) => {
+                println!("Key:    {}", name);
+                println!("Status: Removed Stale Value\n");
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,304 @@
+use std::net::Ipv4Addr;
+
+use crate::util::hex_string;
+
+/// LLC/SNAP PIDs used by SONMP (the OUI is 00:00:81).
+#[allow(non_snake_case)]
+pub mod Pid {
+    pub const FLATNET_HELLO: u16 = 0x01A1;
+    pub const HELLO: u16 = 0x01A2;
+}
+
+/// LLC/SNAP header that precedes every SONMP PDU, without the PID.
+const SNAP_HEADER: [u8; 6] = [0xAA, 0xAA, 0x03, 0x00, 0x00, 0x81];
+
+/// Represents a Nortel/Avaya SynOptics Network Management Protocol hello
+/// received over 802.3/LLC/SNAP.
+pub struct SonmpPdu<'a> {
+    bytes: &'a [u8],
+    pub switch_mac: String,
+    pub switch_ip: String,
+    pub segment: u32, // Segment identifier, the slot and port of the switch
+    pub switch_port: String,
+    pub chassis_type: u8,
+    pub backplane_type: u8,
+    pub state: u8,
+    pub links: u8,
+}
+
+impl<'a> SonmpPdu<'a> {
+    // Attempt to create a new SONMP PDU with the given packet.
+    pub fn new(bytes: &'a [u8]) -> Option<Self> {
+        // 802.3 header (14), LLC/SNAP (8), hello (11)
+        if bytes.len() < 33 || bytes[14..20] != SNAP_HEADER {
+            return None;
+        }
+        match u16::from_be_bytes([bytes[20], bytes[21]]) {
+            Pid::FLATNET_HELLO | Pid::HELLO => {}
+            _ => return None,
+        }
+
+        // IP (4), segment identifier (3), chassis type (1), backplane type (1),
+        // state (1), number of links (1)
+        let hello = &bytes[22..33];
+        let segment = u32::from_be_bytes([0, hello[4], hello[5], hello[6]]);
+        Some(Self {
+            bytes,
+            switch_mac: hex_string(&bytes[6..12]),
+            switch_ip: Ipv4Addr::new(hello[0], hello[1], hello[2], hello[3]).to_string(),
+            segment,
+            switch_port: format!("{}/{}", hello[5], hello[6]),
+            chassis_type: hello[7],
+            backplane_type: hello[8],
+            state: hello[9],
+            links: hello[10],
+        })
+    }
+
+    pub fn switch(&self) -> String {
+        format!("({})", self.switch_ip)
+    }
+
+    pub fn switch_display(&self) -> String {
+        self.switch_ip.to_string()
+    }
+
+    /// Return the chassis model from the S5-CHASSIS-MIB, or the number if it is unknown.
+    pub fn chassis_name(&self) -> String {
+        let name = match self.chassis_type {
+            1 => "Other",
+            2 => "3000",
+            3 => "3030",
+            4 => "2310",
+            5 => "2810",
+            6 => "2912",
+            7 => "2914",
+            8 => "271x",
+            9 => "2813",
+            10 => "2814",
+            11 => "2915",
+            12 => "5000",
+            13 => "2813SA",
+            14 => "2814SA",
+            15 => "810M",
+            16 => "EtherCell",
+            17 => "5005",
+            18 => "Alcatel Ethernet Workgroup Concentrator",
+            20 => "2715SA",
+            21 => "2486",
+            22 => "28000 Series",
+            23 => "23000 Series",
+            24 => "5DN00x Series",
+            25 => "BayStack Ethernet",
+            26 => "23100 Series",
+            27 => "100Base-T Hub",
+            28 => "3000 Fast Ethernet",
+            29 => "Orion Switch",
+            31 => "DDS",
+            32 => "Centillion (6 Slot)",
+            33 => "Centillion (12 Slot)",
+            34 => "Centillion (1 Slot)",
+            35 => "BayStack 301",
+            36 => "BayStack Token Ring Hub",
+            37 => "FVC Multimedia Switch",
+            38 => "Switch Node",
+            39 => "BayStack 302",
+            40 => "BayStack 350",
+            41 => "BayStack 150",
+            42 => "Centillion 50N",
+            43 => "Centillion 50T",
+            44 => "BayStack 303/304",
+            45 => "BayStack 200",
+            46 => "BayStack 250",
+            48 => "BayStack 450",
+            49 => "BayStack 410",
+            50 => "Passport 1200",
+            51 => "Passport 1250",
+            52 => "Passport 1100",
+            53 => "Passport 1150",
+            54 => "Passport 1050",
+            55 => "Passport 1051",
+            56 => "Passport 8610",
+            57 => "Passport 8606",
+            58 => "Passport 8010",
+            59 => "Passport 8006",
+            60 => "BayStack 670",
+            61 => "Passport 740",
+            62 => "Passport 750",
+            63 => "Passport 790",
+            64 => "Business Policy Switch 2000",
+            65 => "Passport 8110",
+            66 => "Passport 8106",
+            67 => "BayStack 3580",
+            68 => "BayStack 10 Power Supply",
+            69 => "BayStack 420",
+            70 => "OPTera Metro 1200",
+            71 => "OPTera 8010co",
+            72 => "OPTera 8610co",
+            73 => "OPTera 8110co",
+            74 => "OPTera 8003",
+            75 => "OPTera 8603",
+            76 => "OPTera 8103",
+            77 => "BayStack 380",
+            78 => "Ethernet Switch 470-48T",
+            79 => "OPTera Metro 1450",
+            80 => "OPTera Metro 1400",
+            81 => "Alteon Switch",
+            82 => "Ethernet Switch 460-24T-PWR",
+            83 => "OPTera Metro 8010",
+            84 => "OPTera Metro 8010co",
+            85 => "OPTera Metro 8006",
+            86 => "OPTera Metro 8003",
+            87 => "Alteon 180e",
+            88 => "Alteon AD3",
+            89 => "Alteon 184",
+            90 => "Alteon AD4",
+            91 => "Passport 1424",
+            92 => "Passport 1648",
+            93 => "Passport 1612",
+            94 => "Passport 1624",
+            95 => "BayStack 380-24F",
+            96 => "Ethernet Routing Switch 5510-24T",
+            97 => "Ethernet Routing Switch 5510-48T",
+            98 => "Ethernet Switch 470-24T",
+            99 => "Wireless LAN Access Point 2220",
+            100 => "Ethernet Routing Switch 2402",
+            101 => "Alteon Application Switch 2424",
+            102 => "Alteon Application Switch 2224",
+            103 => "Alteon Application Switch 2208",
+            104 => "Alteon Application Switch 2216",
+            105 => "Alteon Application Switch 3408",
+            106 => "Alteon Application Switch 3416",
+            107 => "Wireless LAN Security Switch 2250",
+            108 => "Ethernet Switch 425-48T",
+            109 => "Ethernet Switch 425-24T",
+            110 => "Wireless LAN Access Point 2221",
+            111 => "Metro Ethernet Service Unit 24-T SPF",
+            112 => "Metro Ethernet Service Unit 24-T LX DC",
+            113 => "Passport 8300 (10 Slot)",
+            114 => "Passport 8300 (6 Slot)",
+            115 => "Ethernet Routing Switch 5520-24T-PWR",
+            116 => "Ethernet Routing Switch 5520-48T-PWR",
+            117 => "VPN Gateway 3050",
+            118 => "Alteon SSL 310",
+            119 => "Alteon SSL 310 Fiber",
+            120 => "Alteon SSL 310 FIPS",
+            121 => "Alteon SSL 410",
+            122 => "Alteon SSL 410 Fiber",
+            123 => "Alteon Application Switch 2424-SSL",
+            124 => "Ethernet Switch 325-24T",
+            125 => "Ethernet Switch 325-24G",
+            126 => "Wireless LAN Access Point 2225",
+            127 => "Wireless LAN Security Switch 2270",
+            128 => "Ethernet Switch 470-24T-PWR",
+            129 => "Ethernet Switch 470-48T-PWR",
+            130 => "Ethernet Routing Switch 5530-24TFD",
+            131 => "Ethernet Switch 3510-24T",
+            132 => "Metro Ethernet Service Unit 12G AC",
+            133 => "Metro Ethernet Service Unit 12G DC",
+            134 => "Secure Access Switch",
+            135 => "VPN Gateway 3070",
+            136 => "OPTera Metro 3500",
+            137 => "Business Ethernet Switch 1010-24T",
+            138 => "Business Ethernet Switch 1010-48T",
+            139 => "Business Ethernet Switch 1020-24T-PWR",
+            140 => "Business Ethernet Switch 1020-48T-PWR",
+            141 => "Business Ethernet Switch 2010-24T",
+            142 => "Business Ethernet Switch 2010-48T",
+            143 => "Business Ethernet Switch 2020-24T-PWR",
+            144 => "Business Ethernet Switch 2020-48T-PWR",
+            145 => "Business Ethernet Switch 110-24T",
+            146 => "Business Ethernet Switch 110-48T",
+            147 => "Business Ethernet Switch 120-24T-PWR",
+            148 => "Business Ethernet Switch 120-48T-PWR",
+            149 => "Business Ethernet Switch 210-24T",
+            150 => "Business Ethernet Switch 210-48T",
+            151 => "Business Ethernet Switch 220-24T-PWR",
+            152 => "Business Ethernet Switch 220-48T-PWR",
+            153 => "OME 6500",
+            154 => "Ethernet Routing Switch 4548GT",
+            155 => "Ethernet Routing Switch 4548GT-PWR",
+            156 => "Ethernet Routing Switch 4550T",
+            157 => "Ethernet Routing Switch 4550T-PWR",
+            158 => "Ethernet Routing Switch 4526FX",
+            159 => "Ethernet Routing Switch 2500-26T",
+            160 => "Ethernet Routing Switch 2500-26T-PWR",
+            161 => "Ethernet Routing Switch 2500-50T",
+            162 => "Ethernet Routing Switch 2500-50T-PWR",
+            _ => return self.chassis_type.to_string(),
+        };
+        name.to_string()
+    }
+
+    pub fn backplane_name(&self) -> &'static str {
+        match self.backplane_type {
+            1 => "Other",
+            2 => "Ethernet",
+            3 => "Ethernet and Token Ring",
+            4 => "Ethernet and FDDI",
+            5 => "Ethernet, Token Ring and FDDI",
+            6 => "Ethernet and Token Ring with Redundant Power",
+            7 => "Ethernet, Token Ring and FDDI with Redundant Power",
+            8 => "Token Ring",
+            9 => "Ethernet, Token Ring and Fast Ethernet",
+            10 => "Ethernet and Fast Ethernet",
+            11 => "Ethernet, Token Ring and Fast Ethernet with Redundant Power",
+            12 => "Ethernet, Fast Ethernet and Gigabit Ethernet",
+            _ => "Unknown",
+        }
+    }
+
+    pub fn state_name(&self) -> &'static str {
+        match self.state {
+            1 => "Topology Change",
+            2 => "Heartbeat",
+            3 => "New",
+            _ => "Unknown",
+        }
+    }
+
+    pub fn print(&self) {
+        println!();
+        println!("Switch:    {} ({})", self.switch_ip, self.switch_mac);
+        println!(
+            "Port:      {} (Segment {:06X})",
+            self.switch_port, self.segment
+        );
+        println!("Chassis:   {}", self.chassis_name());
+        println!("Backplane: {}", self.backplane_name());
+        println!("State:     {} ({} links)", self.state_name(), self.links);
+        println!("Bytes:     {:02X?}", self.bytes);
+        println!();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(chassis_type: u8) -> Vec<u8> {
+        let mut bytes = vec![0x01, 0x00, 0x81, 0x00, 0x01, 0x00];
+        bytes.extend_from_slice(&[0x00, 0x00, 0x81, 0x11, 0x22, 0x33]);
+        bytes.extend_from_slice(&[0x00, 0x13]);
+        bytes.extend_from_slice(&SNAP_HEADER);
+        bytes.extend_from_slice(&Pid::HELLO.to_be_bytes());
+        bytes.extend_from_slice(&[10, 0, 0, 1, 0x00, 0x01, 0x05, chassis_type, 12, 2, 1]);
+        bytes
+    }
+
+    #[test]
+    fn names_known_chassis_types() {
+        let bytes = frame(96);
+        let pdu = SonmpPdu::new(&bytes).unwrap();
+        assert_eq!(pdu.chassis_name(), "Ethernet Routing Switch 5510-24T");
+        assert_eq!(pdu.state_name(), "Heartbeat");
+        assert_eq!(pdu.switch_port, "1/5");
+    }
+
+    #[test]
+    fn falls_back_to_the_chassis_number() {
+        let bytes = frame(250);
+        let pdu = SonmpPdu::new(&bytes).unwrap();
+        assert_eq!(pdu.chassis_name(), "250");
+    }
+}
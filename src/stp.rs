@@ -0,0 +1,352 @@
+use std::fmt;
+
+use crate::util::hex_string;
+
+#[allow(non_snake_case)]
+pub mod BpduType {
+    pub const CONFIGURATION: u8 = 0x00;
+    pub const RST: u8 = 0x02;
+    pub const TCN: u8 = 0x80;
+}
+
+#[allow(non_snake_case)]
+pub mod Version {
+    pub const STP: u8 = 0x00;
+    pub const RSTP: u8 = 0x02;
+    pub const MSTP: u8 = 0x03;
+}
+
+/// LLC header that precedes every IEEE BPDU (DSAP, SSAP, control).
+const LLC_HEADER: [u8; 3] = [0x42, 0x42, 0x03];
+
+/// LLC/SNAP header that precedes every Cisco PVST+ BPDU (DSAP, SSAP, control, OUI, PID).
+const PVST_SNAP_HEADER: [u8; 8] = [0xAA, 0xAA, 0x03, 0x00, 0x00, 0x0C, 0x01, 0x0B];
+
+/// Bridge priority (4 bits), system ID extension (12 bits), MAC (6).
+pub struct BridgeId {
+    pub priority: u16,
+    pub system_id: u16, // Usually the VLAN or MSTI
+    pub mac: String,
+}
+
+impl BridgeId {
+    fn parse(bytes: &[u8]) -> Self {
+        let priority = u16::from_be_bytes([bytes[0], bytes[1]]);
+        Self {
+            priority: priority & 0xF000,
+            system_id: priority & 0x0FFF,
+            mac: hex_string(&bytes[2..8]),
+        }
+    }
+}
+
+impl fmt::Display for BridgeId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{} {}", self.priority, self.system_id, self.mac)
+    }
+}
+
+/// An MSTI configuration message of an MST BPDU.
+#[allow(dead_code)]
+pub struct Msti {
+    pub msti: u16, // Taken from the regional root's system ID extension
+    pub flags: u8,
+    pub regional_root_id: BridgeId,
+    pub internal_root_path_cost: u32,
+    pub bridge_priority: u8,
+    pub port_priority: u8,
+    pub remaining_hops: u8,
+}
+
+impl fmt::Display for Msti {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "MSTI {}: Root {}, Cost {}, {}",
+            self.msti,
+            self.regional_root_id,
+            self.internal_root_path_cost,
+            StpPdu::port_role_name(self.flags)
+        )
+    }
+}
+
+/// Represents a spanning tree BPDU (STP, RSTP, MSTP or Cisco PVST+).
+pub struct StpPdu<'a> {
+    bytes: &'a [u8],
+    pub pvst: bool,
+    pub version: u8,
+    pub bpdu_type: u8,
+    pub flags: u8,
+    pub root_id: BridgeId,
+    pub root_path_cost: u32,
+    pub bridge_id: BridgeId,
+    pub port_id: u16, // Port priority (4 bits), port number (12 bits)
+    pub mst_config_name: String,
+    pub mst_revision: u16,
+    pub mstis: Vec<Msti>,
+    pub vlan: Option<u16>, // Originating VLAN of a PVST+ BPDU
+}
+
+impl<'a> StpPdu<'a> {
+    // Attempt to create a new BPDU with the given packet.
+    pub fn new(bytes: &'a [u8]) -> Option<Self> {
+        // 802.3 header (14), then either LLC (3) or LLC/SNAP (8)
+        let pvst = bytes.len() >= 22 && bytes[14..22] == PVST_SNAP_HEADER;
+        let bpdu = if pvst {
+            &bytes[22..]
+        } else if bytes.len() >= 17 && bytes[14..17] == LLC_HEADER {
+            &bytes[17..]
+        } else {
+            return None;
+        };
+
+        // Topology change notifications carry no bridge information.
+        if bpdu.len() < 35 || bpdu[0..2] != [0x00, 0x00] || bpdu[3] == BpduType::TCN {
+            return None;
+        }
+
+        // Protocol (2), version (1), type (1), flags (1), root ID (8), root path cost (4),
+        // bridge ID (8), port ID (2), message age (2), max age (2), hello (2), forward delay (2)
+        let mut pdu = Self {
+            bytes,
+            pvst,
+            version: bpdu[2],
+            bpdu_type: bpdu[3],
+            flags: bpdu[4],
+            root_id: BridgeId::parse(&bpdu[5..13]),
+            root_path_cost: u32::from_be_bytes([bpdu[13], bpdu[14], bpdu[15], bpdu[16]]),
+            bridge_id: BridgeId::parse(&bpdu[17..25]),
+            port_id: u16::from_be_bytes([bpdu[25], bpdu[26]]),
+            mst_config_name: String::new(),
+            mst_revision: 0,
+            mstis: Vec::new(),
+            vlan: None,
+        };
+
+        // The BPDU ends after the version 1 length (RSTP) or version 3 data (MSTP).
+        let mut end = match pdu.version {
+            Version::STP => 35,
+            _ => 36,
+        };
+
+        // Version 1 length (1), version 3 length (2), MST configuration ID (51),
+        // CIST internal root path cost (4), CIST bridge ID (8), remaining hops (1)
+        if pdu.version == Version::MSTP && bpdu.len() >= 102 {
+            let v3_len = u16::from_be_bytes([bpdu[36], bpdu[37]]) as usize;
+            end = (38 + v3_len).min(bpdu.len());
+            pdu.mst_config_name = String::from_utf8_lossy(&bpdu[39..71])
+                .trim_end_matches('\0')
+                .to_string();
+            pdu.mst_revision = u16::from_be_bytes([bpdu[71], bpdu[72]]);
+
+            // MSTI configuration messages (16 each)
+            let mut index = 102;
+            while index + 16 <= end {
+                let msti = &bpdu[index..index + 16];
+                let regional_root_id = BridgeId::parse(&msti[1..9]);
+                pdu.mstis.push(Msti {
+                    msti: regional_root_id.system_id,
+                    flags: msti[0],
+                    regional_root_id,
+                    internal_root_path_cost: u32::from_be_bytes([
+                        msti[9], msti[10], msti[11], msti[12],
+                    ]),
+                    bridge_priority: msti[13],
+                    port_priority: msti[14],
+                    remaining_hops: msti[15],
+                });
+                index += 16;
+            }
+        }
+
+        // PVST+ appends the originating VLAN TLV: type (2), length (2), VLAN (2)
+        if pdu.pvst && bpdu.len() >= end + 6 {
+            let tlv = &bpdu[end..end + 6];
+            if tlv[0..4] == [0x00, 0x00, 0x00, 0x02] {
+                pdu.vlan = Some(u16::from_be_bytes([tlv[4], tlv[5]]));
+            }
+        }
+
+        Some(pdu)
+    }
+
+    /// Port priority of the sending port.
+    pub fn port_priority(&self) -> u16 {
+        self.port_id >> 12
+    }
+
+    /// Port number of the sending port.
+    pub fn port_number(&self) -> u16 {
+        self.port_id & 0x0FFF
+    }
+
+    pub fn port_role(&self) -> &'static str {
+        match self.bpdu_type {
+            BpduType::RST => Self::port_role_name(self.flags),
+            // Only designated ports send configuration BPDUs.
+            BpduType::CONFIGURATION => "Designated",
+            _ => "Unknown",
+        }
+    }
+
+    /// Port role encoded in bits 2-3 of RST/MST flags.
+    fn port_role_name(flags: u8) -> &'static str {
+        match (flags >> 2) & 0x03 {
+            1 => "Alternate/Backup",
+            2 => "Root",
+            3 => "Designated",
+            _ => "Unknown",
+        }
+    }
+
+    /// Names of the flags that are set.
+    pub fn flag_names(&self) -> Vec<&'static str> {
+        const NAMES: [(u8, &str); 6] = [
+            (0x01, "Topology Change"),
+            (0x02, "Proposal"),
+            (0x10, "Learning"),
+            (0x20, "Forwarding"),
+            (0x40, "Agreement"),
+            (0x80, "Topology Change Ack"),
+        ];
+        NAMES
+            .iter()
+            .filter(|(flag, _)| self.flags & flag != 0)
+            .map(|(_, name)| *name)
+            .collect()
+    }
+
+    pub fn protocol_name(&self) -> &'static str {
+        match (self.pvst, self.version) {
+            (true, Version::STP) => "PVST+",
+            (true, _) => "Rapid PVST+",
+            (false, Version::STP) => "STP",
+            (false, Version::RSTP) => "RSTP",
+            (false, Version::MSTP) => "MSTP",
+            _ => "Unknown",
+        }
+    }
+
+    /// The sending bridge stands in for the switch when no discovery protocol is found.
+    pub fn switch(&self) -> String {
+        format!("{} ({})", self.bridge_id.mac, self.protocol_name())
+    }
+
+    pub fn switch_display(&self) -> String {
+        self.bridge_id.mac.to_string()
+    }
+
+    pub fn switch_port(&self) -> String {
+        self.port_number().to_string()
+    }
+
+    pub fn vlan(&self) -> String {
+        match self.vlan {
+            Some(vlan) => vlan.to_string(),
+            None => String::new(),
+        }
+    }
+
+    pub fn print(&self) {
+        println!();
+        println!("Protocol: {}", self.protocol_name());
+        println!("Root:     {} (Cost {})", self.root_id, self.root_path_cost);
+        println!("Bridge:   {}", self.bridge_id);
+        println!(
+            "Port:     {} (Priority {}, {})",
+            self.port_number(),
+            self.port_priority(),
+            self.port_role()
+        );
+        println!("Flags:    {}", self.flag_names().join(", "));
+        if self.version == Version::MSTP {
+            println!(
+                "Region:   {} (Revision {})",
+                self.mst_config_name, self.mst_revision
+            );
+            for msti in &self.mstis {
+                println!("          {}", msti);
+            }
+        }
+        if let Some(vlan) = self.vlan {
+            println!("Vlan:     {}", vlan);
+        }
+        println!("Bytes:    {:02X?}", self.bytes);
+        println!();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An MST BPDU with the given MSTI configuration messages and trailing padding.
+    fn mst_frame(mstis: &[[u8; 16]]) -> Vec<u8> {
+        let mut bpdu = vec![0x00, 0x00, Version::MSTP, BpduType::RST, 0x7C];
+        bpdu.extend_from_slice(&[0x80, 0x00, 0x00, 0x11, 0x22, 0x33, 0x44, 0x55]);
+        bpdu.extend_from_slice(&20000u32.to_be_bytes());
+        bpdu.extend_from_slice(&[0x80, 0x00, 0x00, 0xAA, 0xBB, 0xCC, 0xDD, 0xEE]);
+        bpdu.extend_from_slice(&[0x80, 0x05, 0, 0, 20, 0, 2, 0, 15, 0, 0]);
+        bpdu.extend_from_slice(&((64 + 16 * mstis.len()) as u16).to_be_bytes());
+
+        // MST configuration ID: format selector, name (32), revision (2), digest (16)
+        let mut name = [0u8; 32];
+        name[..6].copy_from_slice(b"REGION");
+        bpdu.push(0);
+        bpdu.extend_from_slice(&name);
+        bpdu.extend_from_slice(&7u16.to_be_bytes());
+        bpdu.extend_from_slice(&[0; 16]);
+
+        // CIST internal root path cost, CIST bridge ID, remaining hops
+        bpdu.extend_from_slice(&[0; 13]);
+        for msti in mstis {
+            bpdu.extend_from_slice(msti);
+        }
+        bpdu.extend_from_slice(&[0xFF; 16]);
+
+        let mut bytes = vec![0x01, 0x80, 0xC2, 0x00, 0x00, 0x00];
+        bytes.extend_from_slice(&[0x00, 0x11, 0x22, 0x33, 0x44, 0x55]);
+        bytes.extend_from_slice(&((LLC_HEADER.len() + bpdu.len()) as u16).to_be_bytes());
+        bytes.extend_from_slice(&LLC_HEADER);
+        bytes.extend(bpdu);
+        bytes
+    }
+
+    #[test]
+    fn decodes_msti_messages() {
+        let first = [
+            0x3C, 0x80, 0x0A, 0x00, 0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0x00, 0x00, 0x4E, 0x20, 0x80,
+            0x90, 18,
+        ];
+        let second = [
+            0x78, 0x60, 0x14, 0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x00, 0x00, 0x07, 0xD0, 0x90,
+            0x80, 20,
+        ];
+        let bytes = mst_frame(&[first, second]);
+        let pdu = StpPdu::new(&bytes).unwrap();
+
+        assert_eq!(pdu.mst_config_name, "REGION");
+        assert_eq!(pdu.mst_revision, 7);
+        assert_eq!(pdu.root_path_cost, 20000);
+        // The padding after the version 3 length isn't an MSTI.
+        assert_eq!(pdu.mstis.len(), 2);
+
+        let msti = &pdu.mstis[0];
+        assert_eq!(msti.msti, 10);
+        assert_eq!(msti.flags, 0x3C);
+        assert_eq!(msti.regional_root_id.priority, 0x8000);
+        assert_eq!(msti.regional_root_id.mac, "00:AA:BB:CC:DD:EE");
+        assert_eq!(msti.internal_root_path_cost, 20000);
+        assert_eq!(msti.bridge_priority, 0x80);
+        assert_eq!(msti.port_priority, 0x90);
+        assert_eq!(msti.remaining_hops, 18);
+
+        let msti = &pdu.mstis[1];
+        assert_eq!(msti.msti, 20);
+        assert_eq!(msti.regional_root_id.priority, 0x6000);
+        assert_eq!(msti.regional_root_id.mac, "00:11:22:33:44:55");
+        assert_eq!(msti.internal_root_path_cost, 2000);
+        assert_eq!(msti.remaining_hops, 20);
+    }
+}
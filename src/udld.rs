@@ -0,0 +1,225 @@
+#[allow(non_snake_case)]
+pub mod TlvType {
+    pub const DEVICE_ID: u16 = 0x0001;
+    pub const PORT_ID: u16 = 0x0002;
+    pub const ECHO: u16 = 0x0003;
+    pub const MESSAGE_INTERVAL: u16 = 0x0004;
+    pub const TIMEOUT_INTERVAL: u16 = 0x0005;
+    pub const DEVICE_NAME: u16 = 0x0006;
+    pub const SEQUENCE_NUMBER: u16 = 0x0007;
+}
+
+#[allow(non_snake_case)]
+pub mod Opcode {
+    pub const PROBE: u8 = 0x01;
+    pub const ECHO: u8 = 0x02;
+    pub const FLUSH: u8 = 0x03;
+}
+
+/// LLC/SNAP header that precedes every UDLD PDU (DSAP, SSAP, control, OUI, PID).
+/// UDLD shares the CDP destination MAC and is told apart by the PID.
+const SNAP_HEADER: [u8; 8] = [0xAA, 0xAA, 0x03, 0x00, 0x00, 0x0C, 0x01, 0x11];
+
+/// A device and port the sender has heard on the link.
+pub struct UdldEcho {
+    pub device_id: String,
+    pub port_id: String,
+}
+
+/// Represents a Cisco UniDirectional Link Detection PDU received over 802.3/LLC/SNAP.
+#[allow(dead_code)]
+pub struct UdldPdu<'a> {
+    bytes: &'a [u8],
+    valid: bool,
+    pub version: u8,
+    pub opcode: u8,
+    pub flags: u8,
+    pub switch_id: String,
+    pub switch_name: String,
+    pub switch_port: String,
+    pub echoes: Vec<UdldEcho>,
+    pub message_interval: Option<u8>, // Seconds
+    pub timeout_interval: Option<u8>, // Seconds
+    pub sequence_number: Option<u32>,
+}
+
+impl<'a> UdldPdu<'a> {
+    // Attempt to create a new UDLD PDU with the given packet.
+    pub fn new(bytes: &'a [u8]) -> Option<Self> {
+        // 802.3 header (14), LLC/SNAP (8), UDLD header (4)
+        if bytes.len() < 26 || bytes[14..22] != SNAP_HEADER {
+            return None;
+        }
+
+        // The 802.3 length excludes any Ethernet padding after the PDU.
+        let length = u16::from_be_bytes([bytes[12], bytes[13]]) as usize;
+        let end = (14 + length).min(bytes.len());
+
+        // Version (3 bits), opcode (5 bits), flags (1), checksum (2)
+        let mut pdu = Self {
+            bytes,
+            valid: false,
+            version: bytes[22] >> 5,
+            opcode: bytes[22] & 0x1F,
+            flags: bytes[23],
+            switch_id: String::new(),
+            switch_name: String::new(),
+            switch_port: String::new(),
+            echoes: Vec::new(),
+            message_interval: None,
+            timeout_interval: None,
+            sequence_number: None,
+        };
+
+        // Parse TLVs
+        let mut index = 26;
+        while index + 4 <= end {
+            // Type/Length, the length includes the 4 header bytes.
+            let t = u16::from_be_bytes([bytes[index], bytes[index + 1]]);
+            let l = u16::from_be_bytes([bytes[index + 2], bytes[index + 3]]) as usize;
+            if l < 4 || (index + l) > end {
+                break;
+            }
+            let value = &bytes[index + 4..index + l];
+
+            match t {
+                TlvType::DEVICE_ID => {
+                    pdu.switch_id = String::from_utf8_lossy(value).to_string();
+                    pdu.valid = true;
+                }
+                TlvType::PORT_ID => {
+                    pdu.switch_port = String::from_utf8_lossy(value).to_string();
+                }
+                TlvType::ECHO => pdu.echoes = Self::parse_echoes(value),
+                TlvType::MESSAGE_INTERVAL if l > 4 => pdu.message_interval = Some(value[0]),
+                TlvType::TIMEOUT_INTERVAL if l > 4 => pdu.timeout_interval = Some(value[0]),
+                TlvType::DEVICE_NAME => {
+                    pdu.switch_name = String::from_utf8_lossy(value).to_string();
+                }
+                TlvType::SEQUENCE_NUMBER if l == 8 => {
+                    pdu.sequence_number =
+                        Some(u32::from_be_bytes([value[0], value[1], value[2], value[3]]));
+                }
+                _ => {}
+            }
+
+            // Go to the next TLV
+            index += l;
+        }
+
+        if pdu.valid {
+            Some(pdu)
+        } else {
+            None
+        }
+    }
+
+    /// Number of pairs (4), then a length (2) and value for each device ID and port ID.
+    fn parse_echoes(bytes: &[u8]) -> Vec<UdldEcho> {
+        let mut echoes = Vec::new();
+        if bytes.len() < 4 {
+            return echoes;
+        }
+
+        let pairs = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        let mut index = 4;
+        for _ in 0..pairs {
+            let mut ids = Vec::new();
+            for _ in 0..2 {
+                if index + 2 > bytes.len() {
+                    return echoes;
+                }
+                let l = u16::from_be_bytes([bytes[index], bytes[index + 1]]) as usize;
+                if index + 2 + l > bytes.len() {
+                    return echoes;
+                }
+                ids.push(String::from_utf8_lossy(&bytes[index + 2..index + 2 + l]).to_string());
+                index += 2 + l;
+            }
+            echoes.push(UdldEcho {
+                device_id: ids.remove(0),
+                port_id: ids.remove(0),
+            });
+        }
+        echoes
+    }
+
+    /// Check if any of the given device IDs are in the echo list, meaning the
+    /// switch can hear them. Case and MAC separators are ignored.
+    pub fn echoes(&self, device_ids: &[String]) -> bool {
+        let normalize = |id: &str| -> String {
+            id.chars()
+                .filter(|c| c.is_alphanumeric())
+                .collect::<String>()
+                .to_lowercase()
+        };
+        self.echoes.iter().any(|echo| {
+            device_ids
+                .iter()
+                .any(|id| normalize(id) == normalize(&echo.device_id))
+        })
+    }
+
+    pub fn opcode_name(&self) -> &'static str {
+        match self.opcode {
+            Opcode::PROBE => "Probe",
+            Opcode::ECHO => "Echo",
+            Opcode::FLUSH => "Flush",
+            _ => "Unknown",
+        }
+    }
+
+    pub fn switch(&self) -> String {
+        if self.switch_name.is_empty() {
+            self.switch_id.to_string()
+        } else {
+            format!("{} ({})", self.switch_name, self.switch_id)
+        }
+    }
+
+    pub fn print(&self) {
+        println!();
+        println!("UDLD:     v{} {}", self.version, self.opcode_name());
+        println!("Switch:   {}", self.switch());
+        println!("Port:     {}", self.switch_port);
+        for echo in &self.echoes {
+            println!("Echo:     {} {}", echo.device_id, echo.port_id);
+        }
+        if let (Some(message), Some(timeout)) = (self.message_interval, self.timeout_interval) {
+            println!("Interval: {}s (Timeout {}s)", message, timeout);
+        }
+        println!("Bytes:    {:02X?}", self.bytes);
+        println!();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(header: u8) -> Vec<u8> {
+        let mut bytes = vec![0x01, 0x00, 0x0C, 0xCC, 0xCC, 0xCC];
+        bytes.extend_from_slice(&[0x00, 0x11, 0x22, 0x33, 0x44, 0x55]);
+        bytes.extend_from_slice(&[0, 0]); // Length, set below
+        bytes.extend_from_slice(&SNAP_HEADER);
+        bytes.extend_from_slice(&[header, 0x01, 0, 0]);
+        bytes.extend_from_slice(&[0x00, 0x01, 0x00, 0x07, b'S', b'W', b'1']);
+        let length = (bytes.len() - 14) as u16;
+        bytes[12..14].copy_from_slice(&length.to_be_bytes());
+        bytes
+    }
+
+    #[test]
+    fn splits_the_header_byte() {
+        let bytes = frame(0x21);
+        let pdu = UdldPdu::new(&bytes).unwrap();
+        assert_eq!(pdu.version, 1);
+        assert_eq!(pdu.opcode, Opcode::PROBE);
+        assert_eq!(pdu.switch_id, "SW1");
+
+        let bytes = frame(0x22);
+        let pdu = UdldPdu::new(&bytes).unwrap();
+        assert_eq!(pdu.version, 1);
+        assert_eq!(pdu.opcode, Opcode::ECHO);
+    }
+}
@@ -0,0 +1,19 @@
+/// Format bytes as colon separated hex, e.g. "00:11:22:AA:BB:CC".
+pub fn hex_string(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|byte| format!("{:02X}", byte))
+        .collect::<Vec<String>>()
+        .join(":")
+}
+
+/// Remove the alphabetic characters from a port name, e.g. "GigabitEthernet1/0/1" -> "1/0/1".
+pub fn remove_chars(str: &str) -> String {
+    let mut new_str = String::new();
+    for c in str.chars() {
+        if !c.is_alphabetic() {
+            new_str.push(c);
+        }
+    }
+    new_str
+}
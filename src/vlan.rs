@@ -0,0 +1,123 @@
+use std::{borrow::Cow, collections::BTreeMap};
+
+#[allow(non_snake_case)]
+pub mod Tpid {
+    pub const CUSTOMER: u16 = 0x8100; // 802.1Q
+    pub const SERVICE: u16 = 0x88A8; // 802.1ad
+    pub const QINQ: u16 = 0x9100; // Pre-standard QinQ
+}
+
+/// An 802.1Q or 802.1ad tag.
+#[allow(dead_code)]
+pub struct VlanTag {
+    pub tpid: u16,
+    pub pcp: u8,
+    pub dei: bool,
+    pub vlan_id: u16, // 0 if the frame is only priority tagged
+}
+
+impl VlanTag {
+    pub fn is_service(&self) -> bool {
+        self.tpid != Tpid::CUSTOMER
+    }
+}
+
+/// Remove any VLAN tags from the packet so the payload starts at byte 14.
+/// The tags are returned outermost first.
+pub fn untag(bytes: &[u8]) -> (Cow<'_, [u8]>, Vec<VlanTag>) {
+    let mut tags = Vec::new();
+
+    // Destination (6), source (6), then TPID (2) and TCI (2) for each tag
+    let mut index = 12;
+    while index + 4 <= bytes.len() {
+        let tpid = u16::from_be_bytes([bytes[index], bytes[index + 1]]);
+        if tpid != Tpid::CUSTOMER && tpid != Tpid::SERVICE && tpid != Tpid::QINQ {
+            break;
+        }
+        let tci = u16::from_be_bytes([bytes[index + 2], bytes[index + 3]]);
+        tags.push(VlanTag {
+            tpid,
+            pcp: (tci >> 13) as u8,
+            dei: tci & 0x1000 != 0,
+            vlan_id: tci & 0x0FFF,
+        });
+        index += 4;
+    }
+
+    if tags.is_empty() {
+        return (Cow::Borrowed(bytes), tags);
+    }
+
+    let mut untagged = Vec::with_capacity(bytes.len() - (index - 12));
+    untagged.extend_from_slice(&bytes[..12]);
+    untagged.extend_from_slice(&bytes[index..]);
+    (Cow::Owned(untagged), tags)
+}
+
+/// Counts the VLAN IDs and PCP values seen during a listen window.
+#[derive(Default)]
+pub struct VlanHistogram {
+    pub frames: u32,
+    pub tagged: u32,
+    pub vlans: BTreeMap<(bool, u16), u32>, // (S-tag, VLAN ID)
+    pub pcps: BTreeMap<u8, u32>,
+}
+
+impl VlanHistogram {
+    pub fn add(&mut self, tags: &[VlanTag]) {
+        self.frames += 1;
+        if tags.is_empty() {
+            return;
+        }
+        self.tagged += 1;
+        for tag in tags {
+            // Priority tagged frames don't belong to a VLAN.
+            if tag.vlan_id != 0 {
+                *self
+                    .vlans
+                    .entry((tag.is_service(), tag.vlan_id))
+                    .or_insert(0) += 1;
+            }
+            *self.pcps.entry(tag.pcp).or_insert(0) += 1;
+        }
+    }
+
+    /// Tagged frames with a VLAN ID mean the port is a trunk rather than an access port.
+    pub fn is_trunk(&self) -> bool {
+        !self.vlans.is_empty()
+    }
+
+    /// Return the VLAN IDs and their frame counts in a String separated
+    /// by a comma if there is more than one.
+    pub fn vlans(&self) -> String {
+        self.vlans
+            .iter()
+            .map(|((service, vlan_id), count)| {
+                if *service {
+                    format!("S-{} ({})", vlan_id, count)
+                } else {
+                    format!("{} ({})", vlan_id, count)
+                }
+            })
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+
+    /// Return the PCP values and their frame counts in a String separated
+    /// by a comma if there is more than one.
+    pub fn pcps(&self) -> String {
+        self.pcps
+            .iter()
+            .map(|(pcp, count)| format!("{} ({})", pcp, count))
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+
+    pub fn print(&self) {
+        println!();
+        println!("Tagged: {} of {} frames", self.tagged, self.frames);
+        println!("Vlans:  {}", self.vlans());
+        println!("PCP:    {}", self.pcps());
+        println!();
+    }
+}